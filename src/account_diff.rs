@@ -0,0 +1,258 @@
+/*
+* Copyright 2018-2020 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use std::{collections::HashSet, fmt};
+
+use ton_types::{Cell, CellType, Result, UInt256};
+
+use crate::{
+    accounts::{Account, AccountStatus, StorageUsed},
+    merkle_update::MerkleUpdate,
+    messages::{MsgAddressInt, SimpleLib},
+    types::{Grams, VarUInteger32},
+};
+
+///
+/// Old and new values of the changed field
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FieldChange<T> {
+    pub old: T,
+    pub new: T,
+}
+
+impl<T: PartialEq> FieldChange<T> {
+    fn compare(old: T, new: T) -> Option<Self> {
+        match old == new {
+            true => None,
+            false => Some(Self { old, new })
+        }
+    }
+}
+
+///
+/// Change of balance in one extra currency (None means absent currency)
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExtraCurrencyChange {
+    pub currency_id: u32,
+    pub old: Option<VarUInteger32>,
+    pub new: Option<VarUInteger32>,
+}
+
+///
+/// Change of one library of the account (None means absent library)
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LibraryChange {
+    pub hash: UInt256,
+    pub old: Option<SimpleLib>,
+    pub new: Option<SimpleLib>,
+}
+
+///
+/// Hashes of cells which are present only in the old or only in the new data tree,
+/// computed with Merkle update between the trees
+///
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CellsDiff {
+    pub removed: Vec<UInt256>,
+    pub added: Vec<UInt256>,
+}
+
+impl CellsDiff {
+    pub fn with_roots(old: &Cell, new: &Cell) -> Result<Self> {
+        let update = MerkleUpdate::create(old, new)?;
+        let old_cells = collect_cells(&update.old);
+        let new_cells = collect_cells(&update.new);
+        let mut removed = old_cells.difference(&new_cells).cloned().collect::<Vec<_>>();
+        let mut added = new_cells.difference(&old_cells).cloned().collect::<Vec<_>>();
+        removed.sort();
+        added.sort();
+        Ok(Self { removed, added })
+    }
+}
+
+// hashes of original cells for all cells of update tree except pruned branches
+fn collect_cells(root: &Cell) -> HashSet<UInt256> {
+    let mut cells = HashSet::new();
+    let mut visited = HashSet::new();
+    let mut stack = vec![root.clone()];
+    while let Some(cell) = stack.pop() {
+        if cell.cell_type() == CellType::PrunedBranch || !visited.insert(cell.repr_hash()) {
+            continue
+        }
+        cells.insert(cell.hash(0));
+        for i in 0..cell.references_count() {
+            if let Ok(child) = cell.reference(i) {
+                stack.push(child);
+            }
+        }
+    }
+    cells
+}
+
+///
+/// Difference between two states of the account.
+/// Every field is None (or empty) if it is not changed.
+///
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AccountDiff {
+    pub address: Option<FieldChange<Option<MsgAddressInt>>>,
+    pub status: Option<FieldChange<AccountStatus>>,
+    pub balance: Option<FieldChange<Grams>>,
+    /// changes sorted by currency id
+    pub extra_currencies: Vec<ExtraCurrencyChange>,
+    pub code_hash: Option<FieldChange<Option<UInt256>>>,
+    pub data_hash: Option<FieldChange<Option<UInt256>>>,
+    /// changed cells of data, set only if both states have data
+    pub data_cells: Option<CellsDiff>,
+    pub frozen_hash: Option<FieldChange<Option<UInt256>>>,
+    /// changes sorted by library hash
+    pub libraries: Vec<LibraryChange>,
+    pub storage_used: Option<FieldChange<StorageUsed>>,
+    pub last_paid: Option<FieldChange<u32>>,
+    pub due_payment: Option<FieldChange<Option<Grams>>>,
+    pub last_trans_lt: Option<FieldChange<Option<u64>>>,
+    pub init_code_hash: Option<FieldChange<Option<UInt256>>>,
+}
+
+impl AccountDiff {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Account {
+    /// Build difference from self to other state of the account
+    pub fn diff(&self, other: &Account) -> Result<AccountDiff> {
+        let old_balance = self.balance_checked();
+        let new_balance = other.balance_checked();
+        let mut extra_currencies = Vec::new();
+        old_balance.other.scan_diff(&new_balance.other, |currency_id: u32, old, new| {
+            extra_currencies.push(ExtraCurrencyChange { currency_id, old, new });
+            Ok(true)
+        })?;
+        extra_currencies.sort_by_key(|change| change.currency_id);
+
+        let mut libraries = Vec::new();
+        self.libraries().scan_diff(&other.libraries(), |hash: UInt256, old, new| {
+            libraries.push(LibraryChange { hash, old, new });
+            Ok(true)
+        })?;
+        libraries.sort_by(|a, b| a.hash.cmp(&b.hash));
+
+        let data_cells = match (self.get_data(), other.get_data()) {
+            (Some(old), Some(new)) if old.repr_hash() != new.repr_hash() => {
+                Some(CellsDiff::with_roots(&old, &new)?)
+            }
+            _ => None
+        };
+        let old_info = self.storage_info().cloned().unwrap_or_default();
+        let new_info = other.storage_info().cloned().unwrap_or_default();
+
+        Ok(AccountDiff {
+            address: FieldChange::compare(self.get_addr().cloned(), other.get_addr().cloned()),
+            status: FieldChange::compare(self.status(), other.status()),
+            balance: FieldChange::compare(old_balance.grams, new_balance.grams),
+            extra_currencies,
+            code_hash: FieldChange::compare(self.get_code_hash(), other.get_code_hash()),
+            data_hash: FieldChange::compare(self.get_data_hash(), other.get_data_hash()),
+            data_cells,
+            frozen_hash: FieldChange::compare(self.frozen_hash().cloned(), other.frozen_hash().cloned()),
+            libraries,
+            storage_used: FieldChange::compare(old_info.used, new_info.used),
+            last_paid: FieldChange::compare(old_info.last_paid, new_info.last_paid),
+            due_payment: FieldChange::compare(old_info.due_payment, new_info.due_payment),
+            last_trans_lt: FieldChange::compare(self.last_tr_time(), other.last_tr_time()),
+            init_code_hash: FieldChange::compare(self.init_code_hash().cloned(), other.init_code_hash().cloned()),
+        })
+    }
+}
+
+fn fmt_option<T: fmt::Display>(value: &Option<T>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => "none".to_string()
+    }
+}
+
+fn fmt_hash(value: &Option<UInt256>) -> String {
+    match value {
+        Some(value) => format!("{:x}", value),
+        None => "none".to_string()
+    }
+}
+
+fn fmt_library(lib: &Option<SimpleLib>) -> &'static str {
+    match lib {
+        Some(lib) if lib.public => "public",
+        Some(_) => "private",
+        None => "none"
+    }
+}
+
+impl fmt::Display for AccountDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(change) = &self.address {
+            writeln!(f, "~ address: {} -> {}", fmt_option(&change.old), fmt_option(&change.new))?;
+        }
+        if let Some(change) = &self.status {
+            writeln!(f, "~ status: {:?} -> {:?}", change.old, change.new)?;
+        }
+        if let Some(change) = &self.balance {
+            writeln!(f, "~ balance: {} -> {}", change.old, change.new)?;
+        }
+        for change in &self.extra_currencies {
+            writeln!(f, "~ extra currency {}: {} -> {}",
+                change.currency_id, fmt_option(&change.old), fmt_option(&change.new))?;
+        }
+        if let Some(change) = &self.code_hash {
+            writeln!(f, "~ code hash: {} -> {}", fmt_hash(&change.old), fmt_hash(&change.new))?;
+        }
+        if let Some(change) = &self.data_hash {
+            writeln!(f, "~ data hash: {} -> {}", fmt_hash(&change.old), fmt_hash(&change.new))?;
+        }
+        if let Some(cells) = &self.data_cells {
+            for hash in &cells.removed {
+                writeln!(f, "    - data cell {:x}", hash)?;
+            }
+            for hash in &cells.added {
+                writeln!(f, "    + data cell {:x}", hash)?;
+            }
+        }
+        if let Some(change) = &self.frozen_hash {
+            writeln!(f, "~ frozen hash: {} -> {}", fmt_hash(&change.old), fmt_hash(&change.new))?;
+        }
+        for change in &self.libraries {
+            writeln!(f, "~ library {:x}: {} -> {}",
+                change.hash, fmt_library(&change.old), fmt_library(&change.new))?;
+        }
+        if let Some(change) = &self.storage_used {
+            writeln!(f, "~ storage used: {} -> {}", change.old, change.new)?;
+        }
+        if let Some(change) = &self.last_paid {
+            writeln!(f, "~ last paid: {} -> {}", change.old, change.new)?;
+        }
+        if let Some(change) = &self.due_payment {
+            writeln!(f, "~ due payment: {} -> {}", fmt_option(&change.old), fmt_option(&change.new))?;
+        }
+        if let Some(change) = &self.last_trans_lt {
+            writeln!(f, "~ last transaction lt: {} -> {}", fmt_option(&change.old), fmt_option(&change.new))?;
+        }
+        if let Some(change) = &self.init_code_hash {
+            writeln!(f, "~ init code hash: {} -> {}", fmt_hash(&change.old), fmt_hash(&change.new))?;
+        }
+        Ok(())
+    }
+}
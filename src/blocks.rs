@@ -12,9 +12,10 @@
 */
 
 use crate::{
-    config_params::{CatchainConfig, GlobalVersion},
+    config_params::{CatchainConfig, ConfigParams, GlobalVersion},
     define_HashmapE,
     error::BlockError,
+    hashmapaug::HashmapAugType,
     inbound_messages::InMsgDescr,
     master::{BlkMasterInfo, McBlockExtra},
     merkle_update::MerkleUpdate,
@@ -727,6 +728,14 @@ impl Block {
         data
     }
 
+    /// Check the value flow of the block, fail with the first broken equation
+    pub fn check_value_flow(&self) -> Result<()> {
+        match self.read_value_flow()?.check(self)?.into_iter().next() {
+            Some(mismatch) => fail!(BlockError::InvalidData(mismatch.to_string())),
+            None => Ok(())
+        }
+    }
+
     pub fn read_cur_validator_set_and_cc_conf(&self) -> Result<(ValidatorSet, CatchainConfig)> {
         self
             .read_extra()?
@@ -1071,6 +1080,153 @@ impl ValueFlow {
     }
 }
 
+///
+/// Component of the value flow checked by ValueFlow::check
+///
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ValueFlowComponent {
+    /// from_prev_blk + imported + fees_imported + created + minted + recovered
+    /// must be equal to to_next_blk + exported + fees_collected (+ burned)
+    Balance,
+    /// fees_collected must be equal to fees_imported + created (- burned)
+    /// + fees of transactions + fees of imported messages
+    FeesCollected,
+    /// imported must be equal to the value_imported of InMsgDescr
+    Imported,
+    /// exported must be equal to the value of OutMsgDescr
+    Exported,
+    /// only masterchain blocks can have non-zero fees_imported
+    FeesImported,
+    /// only masterchain blocks can have non-zero recovered
+    Recovered,
+    /// only masterchain blocks can have non-zero minted
+    Minted,
+    /// created of masterchain block must be equal to masterchain_block_fee (param 14),
+    /// created of shard block must be equal to basechain_block_fee >> shard prefix length
+    Created,
+}
+
+impl fmt::Display for ValueFlowComponent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            ValueFlowComponent::Balance => "balance",
+            ValueFlowComponent::FeesCollected => "fees_collected",
+            ValueFlowComponent::Imported => "imported",
+            ValueFlowComponent::Exported => "exported",
+            ValueFlowComponent::FeesImported => "fees_imported",
+            ValueFlowComponent::Recovered => "recovered",
+            ValueFlowComponent::Minted => "minted",
+            ValueFlowComponent::Created => "created",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+///
+/// Broken equation of the value flow: expected is calculated from the other
+/// components or from the block, actual is taken from the value flow
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValueFlowMismatch {
+    pub component: ValueFlowComponent,
+    pub expected: CurrencyCollection,
+    pub actual: CurrencyCollection,
+}
+
+impl fmt::Display for ValueFlowMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "value flow {} mismatch: expected {}, actual {}", self.component, self.expected, self.actual)
+    }
+}
+
+fn sum_currencies(values: &[&CurrencyCollection]) -> Result<CurrencyCollection> {
+    let mut sum = CurrencyCollection::default();
+    for value in values {
+        sum.add(value)?;
+    }
+    Ok(sum)
+}
+
+impl ValueFlow {
+    /// Check balance equations of the value flow against the block
+    /// and return all broken ones (empty if the value flow is consistent)
+    pub fn check(&self, block: &Block) -> Result<Vec<ValueFlowMismatch>> {
+        let masterchain = block.read_info()?.shard().is_masterchain();
+        let extra = block.read_extra()?;
+        let import_fees = extra.read_in_msg_descr()?.root_extra().clone();
+        let exported = extra.read_out_msg_descr()?.root_extra().clone();
+        let transaction_fees = extra.read_account_blocks()?.root_extra().clone();
+
+        let mut mismatches = Vec::new();
+        let mut check = |component, expected: CurrencyCollection, actual: &CurrencyCollection| {
+            if &expected != actual {
+                mismatches.push(ValueFlowMismatch { component, expected, actual: actual.clone() });
+            }
+        };
+
+        let income = sum_currencies(&[
+            &self.from_prev_blk, &self.imported, &self.fees_imported,
+            &self.created, &self.minted, &self.recovered
+        ])?;
+        let mut outcome = sum_currencies(&[&self.to_next_blk, &self.exported])?;
+        #[cfg(feature = "ton")]
+        if let Some(burned) = &self.burned {
+            outcome.add(burned)?;
+        }
+        outcome.add(&self.fees_collected)?;
+        check(ValueFlowComponent::Balance, income, &outcome);
+
+        let mut fees = sum_currencies(&[&self.fees_imported, &self.created, &transaction_fees])?;
+        fees.grams.add(&import_fees.fees_collected)?;
+        let fees_collected = self.fees_collected.clone();
+        #[cfg(feature = "ton")]
+        let fees_collected = match &self.burned {
+            Some(burned) => {
+                let mut rest = fees.clone();
+                if rest.sub(burned)? {
+                    fees = rest;
+                    fees_collected
+                } else {
+                    // more is burned than collected: fees are compared with fees_collected + burned
+                    let mut fees_collected = fees_collected;
+                    fees_collected.add(burned)?;
+                    fees_collected
+                }
+            }
+            None => fees_collected
+        };
+        check(ValueFlowComponent::FeesCollected, fees, &fees_collected);
+        check(ValueFlowComponent::Imported, import_fees.value_imported, &self.imported);
+        check(ValueFlowComponent::Exported, exported, &self.exported);
+
+        if !masterchain {
+            check(ValueFlowComponent::FeesImported, CurrencyCollection::default(), &self.fees_imported);
+            check(ValueFlowComponent::Recovered, CurrencyCollection::default(), &self.recovered);
+            check(ValueFlowComponent::Minted, CurrencyCollection::default(), &self.minted);
+        }
+        Ok(mismatches)
+    }
+
+    /// Check the value flow against the block and the config the block was created with:
+    /// in addition to ValueFlow::check created is checked against block creation fee (param 14)
+    pub fn check_with_config(&self, block: &Block, config: &ConfigParams) -> Result<Vec<ValueFlowMismatch>> {
+        let mut mismatches = self.check(block)?;
+        let info = block.read_info()?;
+        let shard = info.shard();
+        // basechain fee is shared by shards, masterchain is always one full shard
+        let fee = config.block_create_fees(shard.is_masterchain())?;
+        let expected = CurrencyCollection::from_grams(Grams::new(fee.as_u128() >> shard.prefix_len())?);
+        if expected != self.created {
+            mismatches.push(ValueFlowMismatch {
+                component: ValueFlowComponent::Created,
+                expected,
+                actual: self.created.clone(),
+            });
+        }
+        Ok(mismatches)
+    }
+}
+
 /*
 ext_blk_ref$_ start_lt:uint64 end_lt:uint64
     seq_no:uint32 hash:uint256 = ExtBlkRef;
@@ -1617,3 +1773,7 @@ impl Deserializable for TopBlockDescrSet {
         Ok(Self { collection })
     }
 }
+
+#[cfg(test)]
+#[path = "tests/test_blocks.rs"]
+mod tests;
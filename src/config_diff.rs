@@ -0,0 +1,220 @@
+/*
+* Copyright 2018-2020 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use std::fmt;
+
+use serde_json::Value;
+use ton_types::{HashmapType, Result, SliceData};
+
+use crate::config_params::{ConfigParamEnum, ConfigParams};
+
+// Parameters which affect validator set computation, consensus or set of enabled features.
+// Key block detection doesn't use this list: any change of config makes a key block.
+// 0, 1 - config and elector addresses
+// 8 - global version and capabilities
+// 9, 10 - mandatory and critical params
+// 11 - voting setup
+// 12 - workchains
+// 15, 16, 17 - elections, validators count and stakes
+// 28, 29 - catchain and consensus
+// 31 - fundamental smart contracts
+// 32 - 37 - previous, current and next validator sets
+// 39 - validator temporary keys
+const IMPORTANT_CONFIG_PARAMS: [u32; 20] = [
+    0, 1, 8, 9, 10, 11, 12, 15, 16, 17, 28, 29, 31, 32, 33, 34, 35, 36, 37, 39
+];
+
+/// Check if the parameter affects validator sets, consensus or enabled features
+pub fn is_important_config_param(index: u32) -> bool {
+    IMPORTANT_CONFIG_PARAMS.contains(&index)
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConfigParamChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+///
+/// Change of one field of typed parameter
+/// path is a dot separated list of JSON field names (array items are written as [i])
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigFieldDelta {
+    pub path: String,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+///
+/// Change of one config parameter
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigParamChange {
+    pub index: u32,
+    pub kind: ConfigParamChangeKind,
+    /// None for added or not parsable parameter
+    pub old: Option<ConfigParamEnum>,
+    /// None for removed or not parsable parameter
+    pub new: Option<ConfigParamEnum>,
+    /// field level delta, empty for added and removed parameters
+    pub fields: Vec<ConfigFieldDelta>,
+    pub important: bool,
+}
+
+///
+/// Semantic difference between two configs
+///
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ConfigParamsDiff {
+    pub config_addr_changed: bool,
+    /// changes sorted by parameter index
+    pub changes: Vec<ConfigParamChange>,
+}
+
+impl ConfigParamsDiff {
+    pub fn is_empty(&self) -> bool {
+        !self.config_addr_changed && self.changes.is_empty()
+    }
+
+    pub fn added(&self) -> Vec<u32> {
+        self.indices(ConfigParamChangeKind::Added)
+    }
+
+    pub fn removed(&self) -> Vec<u32> {
+        self.indices(ConfigParamChangeKind::Removed)
+    }
+
+    pub fn changed(&self) -> Vec<u32> {
+        self.indices(ConfigParamChangeKind::Changed)
+    }
+
+    pub fn change(&self, index: u32) -> Option<&ConfigParamChange> {
+        self.changes.iter().find(|change| change.index == index)
+    }
+
+    pub fn has_important_changes(&self) -> bool {
+        self.config_addr_changed || self.changes.iter().any(|change| change.important)
+    }
+
+    fn indices(&self, kind: ConfigParamChangeKind) -> Vec<u32> {
+        self.changes.iter()
+            .filter(|change| change.kind == kind)
+            .map(|change| change.index)
+            .collect()
+    }
+}
+
+fn param_to_json(param: &Option<ConfigParamEnum>) -> Option<Value> {
+    param.as_ref().and_then(|param| param.to_json().ok())
+}
+
+fn diff_json(path: &str, old: Option<&Value>, new: Option<&Value>, fields: &mut Vec<ConfigFieldDelta>) {
+    if old == new {
+        return
+    }
+    match (old, new) {
+        (Some(Value::Object(old)), Some(Value::Object(new))) => {
+            let mut keys = old.keys().chain(new.keys()).collect::<Vec<_>>();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let path = match path.is_empty() {
+                    true => key.clone(),
+                    false => format!("{}.{}", path, key)
+                };
+                diff_json(&path, old.get(key), new.get(key), fields);
+            }
+        }
+        (Some(Value::Array(old)), Some(Value::Array(new))) => {
+            for i in 0..std::cmp::max(old.len(), new.len()) {
+                diff_json(&format!("{}[{}]", path, i), old.get(i), new.get(i), fields);
+            }
+        }
+        (old, new) => fields.push(ConfigFieldDelta {
+            path: path.to_string(),
+            old: old.cloned(),
+            new: new.cloned(),
+        })
+    }
+}
+
+fn read_param(index: u32, value: Option<SliceData>) -> Option<ConfigParamEnum> {
+    let cell = value?.reference_opt(0)?;
+    ConfigParamEnum::construct_from_cell_and_number(cell, index).ok()
+}
+
+impl ConfigParams {
+    /// Build semantic difference from self to other config
+    pub fn diff(&self, other: &ConfigParams) -> Result<ConfigParamsDiff> {
+        let mut changes = Vec::new();
+        self.config_params.scan_diff(&other.config_params, |mut key, old, new| {
+            let index = key.get_next_u32()?;
+            let kind = match (&old, &new) {
+                (None, Some(_)) => ConfigParamChangeKind::Added,
+                (Some(_), None) => ConfigParamChangeKind::Removed,
+                _ => ConfigParamChangeKind::Changed,
+            };
+            let old = read_param(index, old);
+            let new = read_param(index, new);
+            let mut fields = Vec::new();
+            if kind == ConfigParamChangeKind::Changed {
+                diff_json("", param_to_json(&old).as_ref(), param_to_json(&new).as_ref(), &mut fields);
+            }
+            changes.push(ConfigParamChange {
+                index,
+                kind,
+                old,
+                new,
+                fields,
+                important: is_important_config_param(index),
+            });
+            Ok(true)
+        })?;
+        changes.sort_by_key(|change| change.index);
+        Ok(ConfigParamsDiff {
+            config_addr_changed: self.config_addr != other.config_addr,
+            changes,
+        })
+    }
+}
+
+fn fmt_value(value: &Option<Value>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => "none".to_string()
+    }
+}
+
+impl fmt::Display for ConfigParamsDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.config_addr_changed {
+            writeln!(f, "! config address changed")?;
+        }
+        for change in &self.changes {
+            let mark = if change.important { " (important)" } else { "" };
+            match change.kind {
+                ConfigParamChangeKind::Added => writeln!(f, "+ param {}{}", change.index, mark)?,
+                ConfigParamChangeKind::Removed => writeln!(f, "- param {}{}", change.index, mark)?,
+                ConfigParamChangeKind::Changed => {
+                    writeln!(f, "~ param {}{}", change.index, mark)?;
+                    for field in &change.fields {
+                        writeln!(f, "    {}: {} -> {}", field.path, fmt_value(&field.old), fmt_value(&field.new))?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,836 @@
+/*
+* Copyright 2018-2020 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+//! Human-readable JSON representation of the blockchain config.
+//!
+//! Every parameter is exported as an entry of the `params` object:
+//! `"index": { "value": <typed fields>, "boc": <base64> }`.
+//! `boc` holds the original cell and is written only if the typed value
+//! cannot be serialized back into an identical cell (unknown parameters,
+//! legacy constructors, invalid data). On import the original cell is reused
+//! while the typed value is not changed, so export and import are lossless.
+
+use std::str::FromStr;
+
+use serde_json::{Map, Value};
+use ton_types::{
+    error, fail, BuilderData, Cell, HashmapE, HashmapType, Result, SliceData, UInt256,
+};
+
+use crate::{
+    config_params::*,
+    error::BlockError,
+    signature::{CryptoSignature, SigPubKey},
+    types::{ExtraCurrencyCollection, Grams, Number16, VarUInteger32},
+    validators::{ValidatorDescr, ValidatorSet},
+    Serializable,
+};
+
+///
+/// Conversion of config structures to and from JSON
+///
+pub trait JsonCodec: Sized {
+    fn to_json(&self) -> Result<Value>;
+    fn from_json(value: &Value) -> Result<Self>;
+}
+
+fn invalid_json(what: &str, value: &Value) -> anyhow::Error {
+    error!(BlockError::InvalidData(format!("cannot parse {} from JSON {}", what, value)))
+}
+
+fn field<'a>(value: &'a Value, name: &str) -> Result<&'a Value> {
+    value.get(name).ok_or_else(|| error!(
+        BlockError::InvalidData(format!("JSON field `{}` is missing", name))
+    ))
+}
+
+fn read_field<T: JsonCodec>(value: &Value, name: &str) -> Result<T> {
+    T::from_json(field(value, name)?)
+}
+
+fn as_object(value: &Value) -> Result<&Map<String, Value>> {
+    value.as_object().ok_or_else(|| invalid_json("object", value))
+}
+
+fn as_array(value: &Value) -> Result<&Vec<Value>> {
+    value.as_array().ok_or_else(|| invalid_json("array", value))
+}
+
+fn as_str(value: &Value) -> Result<&str> {
+    value.as_str().ok_or_else(|| invalid_json("string", value))
+}
+
+macro_rules! impl_json_for_int {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl JsonCodec for $t {
+                fn to_json(&self) -> Result<Value> {
+                    Ok(Value::from(*self))
+                }
+                fn from_json(value: &Value) -> Result<Self> {
+                    let number = value.as_i64().map(i128::from)
+                        .or_else(|| value.as_u64().map(i128::from));
+                    number.and_then(|number| <$t>::try_from(number).ok())
+                        .ok_or_else(|| invalid_json(stringify!($t), value))
+                }
+            }
+        )*
+    }
+}
+
+impl_json_for_int!(u8, u16, u32, u64, i32);
+
+impl JsonCodec for bool {
+    fn to_json(&self) -> Result<Value> {
+        Ok(Value::Bool(*self))
+    }
+    fn from_json(value: &Value) -> Result<Self> {
+        value.as_bool().ok_or_else(|| invalid_json("bool", value))
+    }
+}
+
+impl<T: JsonCodec> JsonCodec for Option<T> {
+    fn to_json(&self) -> Result<Value> {
+        match self {
+            Some(value) => value.to_json(),
+            None => Ok(Value::Null)
+        }
+    }
+    fn from_json(value: &Value) -> Result<Self> {
+        match value {
+            Value::Null => Ok(None),
+            value => Ok(Some(T::from_json(value)?))
+        }
+    }
+}
+
+// big numbers are kept as decimal strings to avoid precision loss in JSON tools
+impl JsonCodec for Grams {
+    fn to_json(&self) -> Result<Value> {
+        Ok(Value::String(self.as_u128().to_string()))
+    }
+    fn from_json(value: &Value) -> Result<Self> {
+        Grams::from_str(as_str(value)?)
+    }
+}
+
+impl JsonCodec for VarUInteger32 {
+    fn to_json(&self) -> Result<Value> {
+        Ok(Value::String(self.value().to_string()))
+    }
+    fn from_json(value: &Value) -> Result<Self> {
+        VarUInteger32::from_str(as_str(value)?)
+    }
+}
+
+macro_rules! impl_json_for_number {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl JsonCodec for $t {
+                fn to_json(&self) -> Result<Value> {
+                    Ok(Value::from(self.as_u32()))
+                }
+                fn from_json(value: &Value) -> Result<Self> {
+                    <$t>::new(u32::from_json(value)?)
+                }
+            }
+        )*
+    }
+}
+
+impl_json_for_number!(Number16);
+
+fn bytes_from_json(value: &Value) -> Result<Vec<u8>> {
+    hex::decode(as_str(value)?).map_err(|err| error!(
+        BlockError::InvalidData(format!("error parsing hex string {}: {}", value, err))
+    ))
+}
+
+impl JsonCodec for UInt256 {
+    fn to_json(&self) -> Result<Value> {
+        Ok(Value::String(hex::encode(self.as_slice())))
+    }
+    fn from_json(value: &Value) -> Result<Self> {
+        let bytes = bytes_from_json(value)?;
+        if bytes.len() != 32 {
+            return Err(invalid_json("uint256", value))
+        }
+        Ok(UInt256::from_slice(&bytes))
+    }
+}
+
+impl JsonCodec for SigPubKey {
+    fn to_json(&self) -> Result<Value> {
+        Ok(Value::String(hex::encode(self.as_slice())))
+    }
+    fn from_json(value: &Value) -> Result<Self> {
+        SigPubKey::from_str(as_str(value)?)
+    }
+}
+
+impl JsonCodec for CryptoSignature {
+    fn to_json(&self) -> Result<Value> {
+        Ok(Value::String(hex::encode(self.to_bytes())))
+    }
+    fn from_json(value: &Value) -> Result<Self> {
+        CryptoSignature::from_bytes(&bytes_from_json(value)?)
+    }
+}
+
+// opaque cells are kept as base64 encoded bag of cells
+impl JsonCodec for Cell {
+    fn to_json(&self) -> Result<Value> {
+        Ok(Value::String(base64::encode(ton_types::serialize_toc(self)?)))
+    }
+    fn from_json(value: &Value) -> Result<Self> {
+        let bytes = base64::decode(as_str(value)?)?;
+        ton_types::deserialize_tree_of_cells(&mut bytes.as_slice())
+    }
+}
+
+///
+/// Representation of dictionary key as JSON object key
+///
+trait JsonKey: Sized {
+    fn to_key(&self) -> String;
+    fn from_key(key: &str) -> Result<Self>;
+}
+
+macro_rules! impl_json_key_for_int {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl JsonKey for $t {
+                fn to_key(&self) -> String {
+                    self.to_string()
+                }
+                fn from_key(key: &str) -> Result<Self> {
+                    key.parse().map_err(|_| invalid_json(stringify!($t), &Value::from(key)))
+                }
+            }
+        )*
+    }
+}
+
+impl_json_key_for_int!(u8, u32, i32);
+
+impl JsonKey for UInt256 {
+    fn to_key(&self) -> String {
+        hex::encode(self.as_slice())
+    }
+    fn from_key(key: &str) -> Result<Self> {
+        UInt256::from_json(&Value::from(key))
+    }
+}
+
+macro_rules! impl_json_struct {
+    ( $name:ty { $( $field:ident ),* $(,)? } ) => {
+        impl JsonCodec for $name {
+            fn to_json(&self) -> Result<Value> {
+                let mut map = Map::new();
+                $( map.insert(stringify!($field).to_string(), self.$field.to_json()?); )*
+                Ok(Value::Object(map))
+            }
+            fn from_json(value: &Value) -> Result<Self> {
+                Ok(Self {
+                    $( $field: read_field(value, stringify!($field))?, )*
+                })
+            }
+        }
+    }
+}
+
+// dictionary is exported as object with keys converted to strings
+macro_rules! impl_json_hashmap {
+    ( $name:ty, $key:ty, $value:ty ) => {
+        impl JsonCodec for $name {
+            fn to_json(&self) -> Result<Value> {
+                let mut map = Map::new();
+                self.iterate_with_keys(|key: $key, value: $value| {
+                    map.insert(key.to_key(), value.to_json()?);
+                    Ok(true)
+                })?;
+                Ok(Value::Object(map))
+            }
+            fn from_json(value: &Value) -> Result<Self> {
+                let mut result = <$name>::default();
+                for (key, value) in as_object(value)? {
+                    result.set(&<$key>::from_key(key)?, &<$value>::from_json(value)?)?;
+                }
+                Ok(result)
+            }
+        }
+    }
+}
+
+// dictionary without values is exported as array of keys
+macro_rules! impl_json_hashset {
+    ( $name:ty, $key:ty ) => {
+        impl JsonCodec for $name {
+            fn to_json(&self) -> Result<Value> {
+                let mut keys = Vec::new();
+                self.iterate_keys(|key: $key| {
+                    keys.push(key.to_json()?);
+                    Ok(true)
+                })?;
+                Ok(Value::Array(keys))
+            }
+            fn from_json(value: &Value) -> Result<Self> {
+                let mut result = <$name>::default();
+                for key in as_array(value)? {
+                    result.add_key(&<$key>::from_json(key)?)?;
+                }
+                Ok(result)
+            }
+        }
+    }
+}
+
+impl_json_hashmap!(ExtraCurrencyCollection, u32, VarUInteger32);
+impl_json_hashmap!(ConfigParam18Map, u32, StoragePrices);
+impl_json_hashmap!(Workchains, i32, WorkchainDescr);
+impl_json_hashmap!(ValidatorKeys, UInt256, ValidatorSignedTempKey);
+impl_json_hashmap!(LicenseRates, u8, u8);
+impl_json_hashmap!(PrecompiledContracts, UInt256, PrecompiledSmc);
+impl_json_hashmap!(BridgeOracles, UInt256, UInt256);
+
+impl_json_hashset!(MandatoryParams, u32);
+impl_json_hashset!(FundamentalSmcAddresses, UInt256);
+impl_json_hashset!(SuspendedAddresses, SuspendedAddressesKey);
+
+impl_json_struct!(ConfigParam0 { config_addr });
+impl_json_struct!(ConfigParam1 { elector_addr });
+impl_json_struct!(ConfigParam2 { minter_addr });
+impl_json_struct!(ConfigParam3 { fee_collector_addr });
+impl_json_struct!(ConfigParam4 { dns_root_addr });
+impl_json_struct!(ConfigParam5 { owner_addr });
+impl_json_struct!(ConfigParam6 { mint_new_price, mint_add_price });
+impl_json_struct!(ConfigParam7 { to_mint });
+impl_json_struct!(GlobalVersion { version, capabilities });
+impl_json_struct!(ConfigParam8 { global_version });
+impl_json_struct!(ConfigParam9 { mandatory_params });
+impl_json_struct!(ConfigParam10 { critical_params });
+impl_json_struct!(ConfigParam12 { workchains });
+impl_json_struct!(ConfigParam13 { cell });
+impl_json_struct!(BlockCreateFees { masterchain_block_fee, basechain_block_fee });
+impl_json_struct!(ConfigParam14 { block_create_fees });
+impl_json_struct!(ConfigParam15 {
+    validators_elected_for, elections_start_before, elections_end_before, stake_held_for
+});
+impl_json_struct!(ConfigParam16 { max_validators, max_main_validators, min_validators });
+impl_json_struct!(ConfigParam17 { min_stake, max_stake, min_total_stake, max_stake_factor });
+impl_json_struct!(StoragePrices {
+    utime_since, bit_price_ps, cell_price_ps, mc_bit_price_ps, mc_cell_price_ps
+});
+impl_json_struct!(ConfigParam18 { map });
+impl_json_struct!(ConfigParam19 { global_id });
+impl_json_struct!(ConfigParam26 { cell });
+impl_json_struct!(ConfigParam27 { cell });
+impl_json_struct!(MsgForwardPrices {
+    lump_price, bit_price, cell_price, ihr_price_factor, first_frac, next_frac
+});
+impl_json_struct!(CatchainConfig {
+    isolate_mc_validators, shuffle_mc_validators, mc_catchain_lifetime,
+    shard_catchain_lifetime, shard_validators_lifetime, shard_validators_num
+});
+impl_json_struct!(ConsensusConfig {
+    new_catchain_ids, round_candidates, next_candidate_delay_ms, consensus_timeout_ms,
+    fast_attempts, attempt_duration, catchain_max_deps, max_block_bytes, max_collated_bytes
+});
+impl_json_struct!(ConfigParam29 { consensus_config });
+impl_json_struct!(DelectorParams {
+    delections_step, validator_init_code_hash, staker_init_code_hash
+});
+impl_json_struct!(ConfigParam31 { fundamental_smc_addr });
+impl_json_struct!(ConfigParam32 { prev_validators });
+impl_json_struct!(ConfigParam33 { prev_temp_validators });
+impl_json_struct!(ConfigParam34 { cur_validators });
+impl_json_struct!(ConfigParam35 { cur_temp_validators });
+impl_json_struct!(ConfigParam36 { next_validators });
+impl_json_struct!(ConfigParam37 { next_temp_validators });
+impl_json_struct!(ConfigParam38 { cell });
+impl_json_struct!(ConfigParam39 { validator_keys });
+impl_json_struct!(ConfigParam40 { slashing_config });
+impl_json_struct!(ConfigParam41 { cell });
+impl_json_struct!(SlashingConfig {
+    slashing_period_mc_blocks_count, resend_mc_blocks_count, min_samples_count,
+    collations_score_weight, signing_score_weight, min_slashing_protection_score,
+    z_param_numerator, z_param_denominator
+});
+impl_json_struct!(ConfigCopyleft { copyleft_reward_threshold, license_rates });
+impl_json_struct!(SizeLimitsAccState {
+    max_acc_state_cells, max_acc_state_bits, max_acc_public_libraries, defer_out_queue_size_limit
+});
+impl_json_struct!(SizeLimitsConfig {
+    max_msg_bits, max_msg_cells, max_library_cells, max_vm_data_depth,
+    max_ext_msg_size, max_ext_msg_depth, acc_state_limits
+});
+impl_json_struct!(SuspendedAddressesKey { workchain_id, address });
+impl_json_struct!(PrecompiledSmc { gas_usage });
+impl_json_struct!(PrecompiledContractsConfig { list });
+impl_json_struct!(OracleBridgeParams {
+    bridge_address, oracle_multisig_address, oracles, external_chain_address
+});
+impl_json_struct!(JettonBridgePrices {
+    bridge_burn_fee, bridge_mint_fee, wallet_min_tons_for_storage,
+    wallet_gas_consumption, minter_min_tons_for_storage, discover_gas_consumption
+});
+impl_json_struct!(JettonBridgeParams {
+    bridge_address, oracles_address, oracles, state_flags, fees
+});
+impl_json_struct!(WorkchainFormat1 { vm_version, vm_mode });
+impl_json_struct!(ConfigProposalSetup {
+    min_tot_rounds, max_tot_rounds, min_wins, max_losses,
+    min_store_sec, max_store_sec, bit_price, cell_price
+});
+
+impl JsonCodec for JettonBridgeFees {
+    fn to_json(&self) -> Result<Value> {
+        let mut map = Map::new();
+        match self {
+            JettonBridgeFees::V0 { burn_bridge_fee } => {
+                map.insert("version".to_string(), Value::from(0));
+                map.insert("burn_bridge_fee".to_string(), burn_bridge_fee.to_json()?);
+            }
+            JettonBridgeFees::V1 { prices, external_chain_address } => {
+                map.insert("version".to_string(), Value::from(1));
+                map.insert("prices".to_string(), prices.to_json()?);
+                map.insert("external_chain_address".to_string(), external_chain_address.to_json()?);
+            }
+        }
+        Ok(Value::Object(map))
+    }
+    fn from_json(value: &Value) -> Result<Self> {
+        match read_field::<u8>(value, "version")? {
+            0 => Ok(JettonBridgeFees::V0 {
+                burn_bridge_fee: read_field(value, "burn_bridge_fee")?,
+            }),
+            1 => Ok(JettonBridgeFees::V1 {
+                prices: read_field(value, "prices")?,
+                external_chain_address: read_field(value, "external_chain_address")?,
+            }),
+            _ => Err(invalid_json("JettonBridgeFees", value))
+        }
+    }
+}
+
+impl JsonCodec for GasLimitsPrices {
+    fn to_json(&self) -> Result<Value> {
+        let mut map = Map::new();
+        map.insert("gas_price".to_string(), self.gas_price.to_json()?);
+        map.insert("gas_limit".to_string(), self.gas_limit.to_json()?);
+        map.insert("special_gas_limit".to_string(), self.special_gas_limit.to_json()?);
+        map.insert("gas_credit".to_string(), self.gas_credit.to_json()?);
+        map.insert("block_gas_limit".to_string(), self.block_gas_limit.to_json()?);
+        map.insert("freeze_due_limit".to_string(), self.freeze_due_limit.to_json()?);
+        map.insert("delete_due_limit".to_string(), self.delete_due_limit.to_json()?);
+        map.insert("flat_gas_limit".to_string(), self.flat_gas_limit.to_json()?);
+        map.insert("flat_gas_price".to_string(), self.flat_gas_price.to_json()?);
+        Ok(Value::Object(map))
+    }
+    fn from_json(value: &Value) -> Result<Self> {
+        // max_gas_threshold is not serialized, it is computed from other fields
+        let mut prices = GasLimitsPrices {
+            gas_price: read_field(value, "gas_price")?,
+            gas_limit: read_field(value, "gas_limit")?,
+            special_gas_limit: read_field(value, "special_gas_limit")?,
+            gas_credit: read_field(value, "gas_credit")?,
+            block_gas_limit: read_field(value, "block_gas_limit")?,
+            freeze_due_limit: read_field(value, "freeze_due_limit")?,
+            delete_due_limit: read_field(value, "delete_due_limit")?,
+            flat_gas_limit: read_field(value, "flat_gas_limit")?,
+            flat_gas_price: read_field(value, "flat_gas_price")?,
+            max_gas_threshold: 0,
+        };
+        prices.max_gas_threshold = prices.calc_max_gas_threshold();
+        Ok(prices)
+    }
+}
+
+impl JsonCodec for ParamLimits {
+    fn to_json(&self) -> Result<Value> {
+        let mut map = Map::new();
+        map.insert("underload".to_string(), self.underload().to_json()?);
+        map.insert("soft_limit".to_string(), self.soft_limit().to_json()?);
+        map.insert("hard_limit".to_string(), self.hard_limit().to_json()?);
+        Ok(Value::Object(map))
+    }
+    fn from_json(value: &Value) -> Result<Self> {
+        ParamLimits::with_limits(
+            read_field(value, "underload")?,
+            read_field(value, "soft_limit")?,
+            read_field(value, "hard_limit")?,
+        )
+    }
+}
+
+impl JsonCodec for BlockLimits {
+    fn to_json(&self) -> Result<Value> {
+        let mut map = Map::new();
+        map.insert("bytes".to_string(), self.bytes().to_json()?);
+        map.insert("gas".to_string(), self.gas().to_json()?);
+        map.insert("lt_delta".to_string(), self.lt_delta().to_json()?);
+        Ok(Value::Object(map))
+    }
+    fn from_json(value: &Value) -> Result<Self> {
+        Ok(BlockLimits::with_limits(
+            read_field(value, "bytes")?,
+            read_field(value, "gas")?,
+            read_field(value, "lt_delta")?,
+        ))
+    }
+}
+
+impl JsonCodec for ConfigVotingSetup {
+    fn to_json(&self) -> Result<Value> {
+        let mut map = Map::new();
+        map.insert("normal_params".to_string(), self.read_normal_params()?.to_json()?);
+        map.insert("critical_params".to_string(), self.read_critical_params()?.to_json()?);
+        Ok(Value::Object(map))
+    }
+    fn from_json(value: &Value) -> Result<Self> {
+        ConfigVotingSetup::new(
+            &read_field(value, "normal_params")?,
+            &read_field(value, "critical_params")?,
+        )
+    }
+}
+
+impl JsonCodec for WorkchainFormat0 {
+    fn to_json(&self) -> Result<Value> {
+        let mut map = Map::new();
+        map.insert("min_addr_len".to_string(), self.min_addr_len().to_json()?);
+        map.insert("max_addr_len".to_string(), self.max_addr_len().to_json()?);
+        map.insert("addr_len_step".to_string(), self.addr_len_step().to_json()?);
+        map.insert("workchain_type_id".to_string(), self.workchain_type_id().to_json()?);
+        Ok(Value::Object(map))
+    }
+    fn from_json(value: &Value) -> Result<Self> {
+        WorkchainFormat0::with_params(
+            read_field(value, "min_addr_len")?,
+            read_field(value, "max_addr_len")?,
+            read_field(value, "addr_len_step")?,
+            read_field(value, "workchain_type_id")?,
+        )
+    }
+}
+
+impl JsonCodec for WorkchainFormat {
+    fn to_json(&self) -> Result<Value> {
+        let mut map = Map::new();
+        match self {
+            WorkchainFormat::Basic(format) => map.insert("basic".to_string(), format.to_json()?),
+            WorkchainFormat::Extended(format) => map.insert("extended".to_string(), format.to_json()?),
+        };
+        Ok(Value::Object(map))
+    }
+    fn from_json(value: &Value) -> Result<Self> {
+        if let Some(format) = value.get("basic") {
+            Ok(WorkchainFormat::Basic(WorkchainFormat1::from_json(format)?))
+        } else if let Some(format) = value.get("extended") {
+            Ok(WorkchainFormat::Extended(WorkchainFormat0::from_json(format)?))
+        } else {
+            Err(invalid_json("WorkchainFormat", value))
+        }
+    }
+}
+
+impl JsonCodec for WorkchainDescr {
+    fn to_json(&self) -> Result<Value> {
+        let mut map = Map::new();
+        map.insert("enabled_since".to_string(), self.enabled_since.to_json()?);
+        map.insert("actual_min_split".to_string(), self.actual_min_split().to_json()?);
+        map.insert("min_split".to_string(), self.min_split().to_json()?);
+        map.insert("max_split".to_string(), self.max_split().to_json()?);
+        map.insert("active".to_string(), self.active.to_json()?);
+        map.insert("accept_msgs".to_string(), self.accept_msgs.to_json()?);
+        map.insert("flags".to_string(), self.flags.to_json()?);
+        map.insert("zerostate_root_hash".to_string(), self.zerostate_root_hash.to_json()?);
+        map.insert("zerostate_file_hash".to_string(), self.zerostate_file_hash.to_json()?);
+        map.insert("version".to_string(), self.version.to_json()?);
+        map.insert("format".to_string(), self.format.to_json()?);
+        Ok(Value::Object(map))
+    }
+    fn from_json(value: &Value) -> Result<Self> {
+        let mut descr = WorkchainDescr::default();
+        descr.enabled_since = read_field(value, "enabled_since")?;
+        descr.active = read_field(value, "active")?;
+        descr.accept_msgs = read_field(value, "accept_msgs")?;
+        descr.flags = read_field(value, "flags")?;
+        descr.zerostate_root_hash = read_field(value, "zerostate_root_hash")?;
+        descr.zerostate_file_hash = read_field(value, "zerostate_file_hash")?;
+        descr.version = read_field(value, "version")?;
+        descr.format = read_field(value, "format")?;
+        descr.set_min_split(read_field(value, "min_split")?)?;
+        descr.set_max_split(read_field(value, "max_split")?)?;
+        descr.set_actual_min_split(read_field(value, "actual_min_split")?)?;
+        Ok(descr)
+    }
+}
+
+impl JsonCodec for ValidatorTempKey {
+    fn to_json(&self) -> Result<Value> {
+        let mut map = Map::new();
+        map.insert("adnl_addr".to_string(), self.adnl_addr().to_json()?);
+        map.insert("temp_public_key".to_string(), self.temp_public_key().to_json()?);
+        map.insert("seqno".to_string(), self.seqno().to_json()?);
+        map.insert("valid_until".to_string(), self.valid_until().to_json()?);
+        Ok(Value::Object(map))
+    }
+    fn from_json(value: &Value) -> Result<Self> {
+        Ok(ValidatorTempKey::with_params(
+            read_field(value, "adnl_addr")?,
+            read_field(value, "temp_public_key")?,
+            read_field(value, "seqno")?,
+            read_field(value, "valid_until")?,
+        ))
+    }
+}
+
+impl JsonCodec for ValidatorSignedTempKey {
+    fn to_json(&self) -> Result<Value> {
+        let mut map = Map::new();
+        map.insert("key".to_string(), self.key().to_json()?);
+        map.insert("signature".to_string(), self.signature().to_json()?);
+        Ok(Value::Object(map))
+    }
+    fn from_json(value: &Value) -> Result<Self> {
+        Ok(ValidatorSignedTempKey::with_key_and_signature(
+            read_field(value, "key")?,
+            read_field(value, "signature")?,
+        ))
+    }
+}
+
+impl JsonCodec for ValidatorDescr {
+    fn to_json(&self) -> Result<Value> {
+        let mut map = Map::new();
+        map.insert("public_key".to_string(), self.public_key.to_json()?);
+        map.insert("weight".to_string(), self.weight.to_json()?);
+        map.insert("adnl_addr".to_string(), self.adnl_addr.to_json()?);
+        map.insert("mc_seq_no_since".to_string(), self.mc_seq_no_since.to_json()?);
+        Ok(Value::Object(map))
+    }
+    fn from_json(value: &Value) -> Result<Self> {
+        // prev_weight_sum is not serialized, it is computed by ValidatorSet
+        Ok(ValidatorDescr {
+            mc_seq_no_since: read_field(value, "mc_seq_no_since")?,
+            ..ValidatorDescr::with_params(
+                read_field(value, "public_key")?,
+                read_field(value, "weight")?,
+                read_field(value, "adnl_addr")?,
+            )
+        })
+    }
+}
+
+impl JsonCodec for ValidatorSet {
+    fn to_json(&self) -> Result<Value> {
+        let mut map = Map::new();
+        map.insert("utime_since".to_string(), self.utime_since().to_json()?);
+        map.insert("utime_until".to_string(), self.utime_until().to_json()?);
+        map.insert("total".to_string(), self.total().to_json()?);
+        map.insert("main".to_string(), self.main().to_json()?);
+        map.insert("total_weight".to_string(), self.total_weight().to_json()?);
+        let list = self.list().iter()
+            .map(|descr| descr.to_json())
+            .collect::<Result<Vec<_>>>()?;
+        map.insert("list".to_string(), Value::Array(list));
+        Ok(Value::Object(map))
+    }
+    fn from_json(value: &Value) -> Result<Self> {
+        let list = as_array(field(value, "list")?)?.iter()
+            .map(ValidatorDescr::from_json)
+            .collect::<Result<Vec<_>>>()?;
+        let vset = ValidatorSet::with_values_version_2(
+            read_field(value, "utime_since")?,
+            read_field(value, "utime_until")?,
+            read_field(value, "main")?,
+            read_field(value, "total_weight")?,
+            list,
+        )?;
+        if vset.total() != read_field::<u16>(value, "total")? {
+            fail!(BlockError::InvalidData(
+                "`total` of validator set must be equal to the length of `list`".to_string()
+            ))
+        }
+        Ok(vset)
+    }
+}
+
+macro_rules! impl_json_for_config_param_enum {
+    ( $( $index:literal => $variant:ident ($t:ty) ),* $(,)? ) => {
+        impl ConfigParamEnum {
+            /// Typed JSON value of the parameter; raw parameters are exported as base64 bag of cells
+            pub fn to_json(&self) -> Result<Value> {
+                match self {
+                    $( ConfigParamEnum::$variant(param) => param.to_json(), )*
+                    ConfigParamEnum::ConfigParamAny(_, slice) => slice.clone().into_cell().to_json(),
+                }
+            }
+
+            /// Read parameter with given index from JSON value
+            pub fn from_json(index: u32, value: &Value) -> Result<Self> {
+                match index {
+                    $( $index => Ok(ConfigParamEnum::$variant(<$t>::from_json(value)?)), )*
+                    index => Ok(ConfigParamEnum::ConfigParamAny(index, SliceData::load_cell(Cell::from_json(value)?)?)),
+                }
+            }
+        }
+    }
+}
+
+impl_json_for_config_param_enum!(
+    0 => ConfigParam0(ConfigParam0),
+    1 => ConfigParam1(ConfigParam1),
+    2 => ConfigParam2(ConfigParam2),
+    3 => ConfigParam3(ConfigParam3),
+    4 => ConfigParam4(ConfigParam4),
+    5 => ConfigParam5(ConfigParam5),
+    6 => ConfigParam6(ConfigParam6),
+    7 => ConfigParam7(ConfigParam7),
+    8 => ConfigParam8(ConfigParam8),
+    9 => ConfigParam9(ConfigParam9),
+    10 => ConfigParam10(ConfigParam10),
+    11 => ConfigParam11(ConfigVotingSetup),
+    12 => ConfigParam12(ConfigParam12),
+    13 => ConfigParam13(ConfigParam13),
+    14 => ConfigParam14(ConfigParam14),
+    15 => ConfigParam15(ConfigParam15),
+    16 => ConfigParam16(ConfigParam16),
+    17 => ConfigParam17(ConfigParam17),
+    18 => ConfigParam18(ConfigParam18),
+    19 => ConfigParam19(ConfigParam19),
+    20 => ConfigParam20(GasLimitsPrices),
+    21 => ConfigParam21(GasLimitsPrices),
+    22 => ConfigParam22(BlockLimits),
+    23 => ConfigParam23(BlockLimits),
+    24 => ConfigParam24(MsgForwardPrices),
+    25 => ConfigParam25(MsgForwardPrices),
+    26 => ConfigParam26(ConfigParam26),
+    27 => ConfigParam27(ConfigParam27),
+    28 => ConfigParam28(CatchainConfig),
+    29 => ConfigParam29(ConfigParam29),
+    30 => ConfigParam30(DelectorParams),
+    31 => ConfigParam31(ConfigParam31),
+    32 => ConfigParam32(ConfigParam32),
+    33 => ConfigParam33(ConfigParam33),
+    34 => ConfigParam34(ConfigParam34),
+    35 => ConfigParam35(ConfigParam35),
+    36 => ConfigParam36(ConfigParam36),
+    37 => ConfigParam37(ConfigParam37),
+    38 => ConfigParam38(ConfigParam38),
+    39 => ConfigParam39(ConfigParam39),
+    40 => ConfigParam40(ConfigParam40),
+    41 => ConfigParam41(ConfigParam41),
+    42 => ConfigParam42(ConfigCopyleft),
+    43 => ConfigParam43(SizeLimitsConfig),
+    44 => ConfigParam44(SuspendedAddresses),
+    45 => ConfigParam45(PrecompiledContractsConfig),
+    71 => ConfigParam71(OracleBridgeParams),
+    72 => ConfigParam72(OracleBridgeParams),
+    73 => ConfigParam73(OracleBridgeParams),
+    79 => ConfigParam79(JettonBridgeParams),
+    80 => ConfigParam80(JettonBridgeParams),
+    81 => ConfigParam81(JettonBridgeParams),
+    82 => ConfigParam82(JettonBridgeParams),
+);
+
+impl ConfigParamEnum {
+    /// Serialize parameter into separate cell (as it is stored in config dictionary)
+    pub fn param_cell(&self) -> Result<Cell> {
+        let mut cell = BuilderData::new();
+        self.write_to_cell(&mut cell)?;
+        cell.into_cell()?.reference(0)
+    }
+}
+
+impl JsonCodec for ConfigParams {
+    fn to_json(&self) -> Result<Value> {
+        let mut params = Map::new();
+        self.config_params.iterate_slices(|mut key, value| {
+            let index = key.get_next_u32()?;
+            let cell = value.reference_opt(0).ok_or_else(|| error!(
+                BlockError::InvalidData(format!("config param {} has no reference", index))
+            ))?;
+            let mut entry = Map::new();
+            match ConfigParamEnum::construct_from_cell_and_number(cell.clone(), index) {
+                Ok(ConfigParamEnum::ConfigParamAny(..)) | Err(_) => {
+                    entry.insert("boc".to_string(), cell.to_json()?);
+                }
+                Ok(param) => {
+                    entry.insert("value".to_string(), param.to_json()?);
+                    let identical = param.param_cell()
+                        .map(|param_cell| param_cell.repr_hash() == cell.repr_hash())
+                        .unwrap_or(false);
+                    if !identical {
+                        entry.insert("boc".to_string(), cell.to_json()?);
+                    }
+                }
+            }
+            params.insert(index.to_string(), Value::Object(entry));
+            Ok(true)
+        })?;
+        let mut map = Map::new();
+        map.insert("config_addr".to_string(), self.config_addr.to_json()?);
+        map.insert("params".to_string(), Value::Object(params));
+        Ok(Value::Object(map))
+    }
+
+    fn from_json(value: &Value) -> Result<Self> {
+        let mut config_params = HashmapE::with_bit_len(32);
+        for (index, entry) in as_object(field(value, "params")?)? {
+            let index = u32::from_key(index)?;
+            let cell = match (entry.get("value"), entry.get("boc")) {
+                (Some(value), Some(boc)) => {
+                    // original cell is kept until the typed value is edited
+                    let original = Cell::from_json(boc)?;
+                    let param = ConfigParamEnum::from_json(index, value)?;
+                    match ConfigParamEnum::construct_from_cell_and_number(original.clone(), index) {
+                        Ok(original_param) if original_param == param => original,
+                        _ => param.param_cell()?
+                    }
+                }
+                (Some(value), None) => ConfigParamEnum::from_json(index, value)?.param_cell()?,
+                (None, Some(boc)) => Cell::from_json(boc)?,
+                (None, None) => fail!(BlockError::InvalidData(
+                    format!("config param {} has neither `value` nor `boc`", index)
+                ))
+            };
+            let key = SliceData::load_builder(index.write_to_new_cell()?)?;
+            let mut value = BuilderData::new();
+            value.checked_append_reference(cell)?;
+            config_params.set_builder(key, &value)?;
+        }
+        Ok(ConfigParams {
+            config_addr: read_field(value, "config_addr")?,
+            config_params,
+        })
+    }
+}
+
+impl ConfigParams {
+    /// Export config into pretty printed JSON string
+    pub fn to_json_string(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&self.to_json()?)?)
+    }
+
+    /// Import config from JSON string
+    pub fn from_json_str(string: &str) -> Result<Self> {
+        let value: Value = serde_json::from_str(string)?;
+        Self::from_json(&value)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/test_config_json.rs"]
+mod tests;
@@ -11,6 +11,8 @@
 * limitations under the License.
 */
 
+use std::fmt;
+
 use ton_types::{
     BuilderData, Cell, error,
     fail,
@@ -18,10 +20,12 @@ use ton_types::{
 };
 
 use crate::{
+    accounts::{StorageUsed, StorageUsedShort},
     define_HashmapE,
     error::BlockError,
     hashmapaug::HashmapAugType,
-    shard::ShardIdent,
+    messages::MsgAddressInt,
+    shard::{ShardIdent, MASTERCHAIN_ID},
     shard_accounts::ShardAccounts,
     signature::{CryptoSignature, SigPubKey},
     types::{ChildCell, ExtraCurrencyCollection, Grams, Number8, Number12, Number16, Number13, Number32},
@@ -188,13 +192,47 @@ impl ConfigParams {
             _ => fail!("no mandatory params in config")
         }
     }
-    // TODO 11 ConfigVotingSetup
+    pub fn critical_params(&self) -> Result<MandatoryParams> {
+        match self.config(10)? {
+            Some(ConfigParamEnum::ConfigParam10(cp)) => Ok(cp.critical_params),
+            _ => fail!("no critical params in config")
+        }
+    }
+    pub fn config_voting_setup(&self) -> Result<ConfigVotingSetup> {
+        match self.config(11)? {
+            Some(ConfigParamEnum::ConfigParam11(cvs)) => Ok(cvs),
+            _ => fail!("no config voting setup in config")
+        }
+    }
     pub fn workchains(&self) -> Result<Workchains> {
         match self.config(12)? {
             Some(ConfigParamEnum::ConfigParam12(param)) => Ok(param.workchains),
             _ => fail!("Workchains not found in config")
         }
     }
+    ///
+    /// Check the address to be valid in the network with this config.
+    /// Masterchain accepts only standard addresses without anycast,
+    /// other workchains are checked against their descriptions from ConfigParam12.
+    ///
+    pub fn check_address(&self, address: &MsgAddressInt) -> Result<()> {
+        let workchain_id = address.workchain_id();
+        if workchain_id == MASTERCHAIN_ID {
+            match address {
+                MsgAddressInt::AddrStd(addr) if addr.anycast.is_none() => return Ok(()),
+                MsgAddressInt::AddrStd(_) => fail!(BlockError::InvalidArg(
+                    "anycast is not allowed in masterchain".to_string()
+                )),
+                MsgAddressInt::AddrVar(_) => fail!(BlockError::InvalidArg(
+                    "variable address is not allowed in masterchain".to_string()
+                ))
+            }
+        }
+        match self.workchains()?.get(&workchain_id)? {
+            Some(descr) => descr.check_address(address),
+            None => fail!(BlockError::InvalidArg(format!("workchain {} doesn't exist", workchain_id)))
+        }
+    }
     // TODO 13 compliant pricing
     pub fn block_create_fees(&self, masterchain: bool) -> Result<Grams> {
         match self.config(14)? {
@@ -232,6 +270,12 @@ impl ConfigParams {
             _ => fail!("Storage prices not found")
         }
     }
+    pub fn global_id(&self) -> Result<i32> {
+        match self.config(19)? {
+            Some(ConfigParamEnum::ConfigParam19(param)) => Ok(param.global_id),
+            _ => fail!("no global id in config")
+        }
+    }
     pub fn gas_prices(&self, is_masterchain: bool) -> Result<GasLimitsPrices> {
         if is_masterchain {
             if let Some(ConfigParamEnum::ConfigParam20(param)) = self.config(20)? {
@@ -329,12 +373,29 @@ impl ConfigParams {
             self.catchain_config()?
         ))
     }
+    pub fn reserved_param(&self, index: u32) -> Result<Option<Cell>> {
+        match (index, self.config(index)?) {
+            (26, Some(ConfigParamEnum::ConfigParam26(param))) => Ok(Some(param.cell)),
+            (27, Some(ConfigParamEnum::ConfigParam27(param))) => Ok(Some(param.cell)),
+            (38, Some(ConfigParamEnum::ConfigParam38(param))) => Ok(Some(param.cell)),
+            (41, Some(ConfigParamEnum::ConfigParam41(param))) => Ok(Some(param.cell)),
+            (26 | 27 | 38 | 41, None) => Ok(None),
+            (26 | 27 | 38 | 41, _) => fail!("wrong config {} (reserved param)", index),
+            _ => fail!(BlockError::InvalidArg(format!("config {} is not a reserved param", index)))
+        }
+    }
     pub fn copyleft_config(&self) -> Result<ConfigCopyleft> {
         match self.config(42)? {
             Some(ConfigParamEnum::ConfigParam42(cp)) => Ok(cp),
             _ => fail!("no config 42 (copyleft)")
         }
     }
+    pub fn size_limits_config(&self) -> Result<SizeLimitsConfig> {
+        match self.config(43)? {
+            Some(ConfigParamEnum::ConfigParam43(slc)) => Ok(slc),
+            _ => fail!("no config 43 (size limits)")
+        }
+    }
     pub fn suspended_addresses(&self) -> Result<Option<SuspendedAddresses>> {
         match self.config(44)? {
             Some(ConfigParamEnum::ConfigParam44(sa)) => Ok(Some(sa)),
@@ -342,10 +403,38 @@ impl ConfigParams {
             _ =>  fail!("wrong config 44 (suspended addresses)")
         }
     }
+    pub fn precompiled_contracts(&self) -> Result<Option<PrecompiledContractsConfig>> {
+        match self.config(45)? {
+            Some(ConfigParamEnum::ConfigParam45(pc)) => Ok(Some(pc)),
+            None => Ok(None),
+            _ =>  fail!("wrong config 45 (precompiled contracts)")
+        }
+    }
+    pub fn oracle_bridge_params(&self, index: u32) -> Result<Option<OracleBridgeParams>> {
+        match (index, self.config(index)?) {
+            (71, Some(ConfigParamEnum::ConfigParam71(param))) |
+            (72, Some(ConfigParamEnum::ConfigParam72(param))) |
+            (73, Some(ConfigParamEnum::ConfigParam73(param))) => Ok(Some(param)),
+            (71..=73, None) => Ok(None),
+            (71..=73, _) => fail!("wrong config {} (oracle bridge params)", index),
+            _ => fail!(BlockError::InvalidArg(format!("config {} is not an oracle bridge param", index)))
+        }
+    }
+    pub fn token_bridge_params(&self, index: u32) -> Result<Option<JettonBridgeParams>> {
+        match (index, self.config(index)?) {
+            (79, Some(ConfigParamEnum::ConfigParam79(param))) |
+            (80, Some(ConfigParamEnum::ConfigParam80(param))) |
+            (81, Some(ConfigParamEnum::ConfigParam81(param))) |
+            (82, Some(ConfigParamEnum::ConfigParam82(param))) => Ok(Some(param)),
+            (79..=82, None) => Ok(None),
+            (79..=82, _) => fail!("wrong config {} (token bridge params)", index),
+            _ => fail!(BlockError::InvalidArg(format!("config {} is not a token bridge param", index)))
+        }
+    }
     // TODO 39 validator signed temp keys
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[repr(u64)]
 pub enum GlobalCapabilities {
     CapNone                   = 0,
@@ -386,6 +475,238 @@ pub enum GlobalCapabilities {
     CapFastFinality           = 0x0001_0000_0000,
 }
 
+// capabilities which are compiled only with corresponding feature: (bit, name, feature)
+const FEATURE_GATED_CAPABILITIES: [(u64, &str, &str); 3] = [
+    (0x0000_0010_0000, "CapDiff", "gosh"),
+    (0x0000_1000_0000, "CapGroth16", "groth"),
+    (0x0001_0000_0000, "CapFastFinality", "venom"),
+];
+
+impl GlobalCapabilities {
+    /// All capabilities known in current build
+    pub const ALL: &'static [GlobalCapabilities] = &[
+        GlobalCapabilities::CapIhrEnabled,
+        GlobalCapabilities::CapCreateStatsEnabled,
+        GlobalCapabilities::CapBounceMsgBody,
+        GlobalCapabilities::CapReportVersion,
+        GlobalCapabilities::CapSplitMergeTransactions,
+        GlobalCapabilities::CapShortDequeue,
+        GlobalCapabilities::CapMbppEnabled,
+        GlobalCapabilities::CapFastStorageStat,
+        GlobalCapabilities::CapInitCodeHash,
+        GlobalCapabilities::CapOffHypercube,
+        GlobalCapabilities::CapMycode,
+        GlobalCapabilities::CapSetLibCode,
+        GlobalCapabilities::CapFixTupleIndexBug,
+        GlobalCapabilities::CapRemp,
+        GlobalCapabilities::CapDelections,
+        GlobalCapabilities::CapFullBodyInBounced,
+        GlobalCapabilities::CapStorageFeeToTvm,
+        GlobalCapabilities::CapCopyleft,
+        GlobalCapabilities::CapIndexAccounts,
+        #[cfg(feature = "gosh")]
+        GlobalCapabilities::CapDiff,
+        GlobalCapabilities::CapsTvmBugfixes2022,
+        GlobalCapabilities::CapWorkchains,
+        GlobalCapabilities::CapStcontNewFormat,
+        GlobalCapabilities::CapFastStorageStatBugfix,
+        GlobalCapabilities::CapResolveMerkleCell,
+        GlobalCapabilities::CapSignatureWithId,
+        GlobalCapabilities::CapBounceAfterFailedAction,
+        #[cfg(feature = "groth")]
+        GlobalCapabilities::CapGroth16,
+        GlobalCapabilities::CapFeeInGasUnits,
+        GlobalCapabilities::CapBigCells,
+        GlobalCapabilities::CapSuspendedList,
+        #[cfg(feature = "venom")]
+        GlobalCapabilities::CapFastFinality,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GlobalCapabilities::CapNone => "CapNone",
+            GlobalCapabilities::CapIhrEnabled => "CapIhrEnabled",
+            GlobalCapabilities::CapCreateStatsEnabled => "CapCreateStatsEnabled",
+            GlobalCapabilities::CapBounceMsgBody => "CapBounceMsgBody",
+            GlobalCapabilities::CapReportVersion => "CapReportVersion",
+            GlobalCapabilities::CapSplitMergeTransactions => "CapSplitMergeTransactions",
+            GlobalCapabilities::CapShortDequeue => "CapShortDequeue",
+            GlobalCapabilities::CapMbppEnabled => "CapMbppEnabled",
+            GlobalCapabilities::CapFastStorageStat => "CapFastStorageStat",
+            GlobalCapabilities::CapInitCodeHash => "CapInitCodeHash",
+            GlobalCapabilities::CapOffHypercube => "CapOffHypercube",
+            GlobalCapabilities::CapMycode => "CapMycode",
+            GlobalCapabilities::CapSetLibCode => "CapSetLibCode",
+            GlobalCapabilities::CapFixTupleIndexBug => "CapFixTupleIndexBug",
+            GlobalCapabilities::CapRemp => "CapRemp",
+            GlobalCapabilities::CapDelections => "CapDelections",
+            GlobalCapabilities::CapFullBodyInBounced => "CapFullBodyInBounced",
+            GlobalCapabilities::CapStorageFeeToTvm => "CapStorageFeeToTvm",
+            GlobalCapabilities::CapCopyleft => "CapCopyleft",
+            GlobalCapabilities::CapIndexAccounts => "CapIndexAccounts",
+            #[cfg(feature = "gosh")]
+            GlobalCapabilities::CapDiff => "CapDiff",
+            GlobalCapabilities::CapsTvmBugfixes2022 => "CapsTvmBugfixes2022",
+            GlobalCapabilities::CapWorkchains => "CapWorkchains",
+            GlobalCapabilities::CapStcontNewFormat => "CapStcontNewFormat",
+            GlobalCapabilities::CapFastStorageStatBugfix => "CapFastStorageStatBugfix",
+            GlobalCapabilities::CapResolveMerkleCell => "CapResolveMerkleCell",
+            GlobalCapabilities::CapSignatureWithId => "CapSignatureWithId",
+            GlobalCapabilities::CapBounceAfterFailedAction => "CapBounceAfterFailedAction",
+            #[cfg(feature = "groth")]
+            GlobalCapabilities::CapGroth16 => "CapGroth16",
+            GlobalCapabilities::CapFeeInGasUnits => "CapFeeInGasUnits",
+            GlobalCapabilities::CapBigCells => "CapBigCells",
+            GlobalCapabilities::CapSuspendedList => "CapSuspendedList",
+            #[cfg(feature = "venom")]
+            GlobalCapabilities::CapFastFinality => "CapFastFinality",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        if name == "CapNone" {
+            return Some(GlobalCapabilities::CapNone)
+        }
+        Self::ALL.iter().find(|cap| cap.name() == name).copied()
+    }
+}
+
+impl fmt::Display for GlobalCapabilities {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl std::str::FromStr for GlobalCapabilities {
+    type Err = anyhow::Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        Self::from_name(name).ok_or_else(|| error!(
+            BlockError::InvalidArg(format!("unknown capability `{}`", name))
+        ))
+    }
+}
+
+///
+/// Typed set of global capabilities (GlobalVersion::capabilities).
+/// Bits without known capability are kept and reported by unknown_bits.
+///
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CapabilitiesSet(u64);
+
+impl CapabilitiesSet {
+    pub const fn new() -> Self {
+        Self(0)
+    }
+
+    pub const fn with_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    pub const fn bits(&self) -> u64 {
+        self.0
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Mask of all capabilities known in current build
+    pub fn known_bits() -> u64 {
+        GlobalCapabilities::ALL.iter().fold(0, |mask, cap| mask | *cap as u64)
+    }
+
+    /// Set bits without known capability (including capabilities of disabled features)
+    pub fn unknown_bits(&self) -> u64 {
+        self.0 & !Self::known_bits()
+    }
+
+    /// Names and features of set capabilities which are disabled in current build
+    pub fn disabled_features(&self) -> Vec<(&'static str, &'static str)> {
+        let unknown = self.unknown_bits();
+        FEATURE_GATED_CAPABILITIES.iter()
+            .filter(|(bit, _, _)| unknown & bit != 0)
+            .map(|(_, name, feature)| (*name, *feature))
+            .collect()
+    }
+
+    pub fn contains(&self, capability: GlobalCapabilities) -> bool {
+        (self.0 & capability as u64) != 0
+    }
+
+    pub fn insert(&mut self, capability: GlobalCapabilities) {
+        self.0 |= capability as u64
+    }
+
+    pub fn remove(&mut self, capability: GlobalCapabilities) {
+        self.0 &= !(capability as u64)
+    }
+
+    /// Iterate known enabled capabilities
+    pub fn iter(&self) -> impl Iterator<Item = GlobalCapabilities> + '_ {
+        GlobalCapabilities::ALL.iter().copied().filter(move |cap| self.contains(*cap))
+    }
+
+    /// Capabilities enabled (first) and disabled (second) in other set comparing with self
+    pub fn diff(&self, other: &CapabilitiesSet) -> (CapabilitiesSet, CapabilitiesSet) {
+        (CapabilitiesSet(other.0 & !self.0), CapabilitiesSet(self.0 & !other.0))
+    }
+}
+
+impl From<u64> for CapabilitiesSet {
+    fn from(bits: u64) -> Self {
+        Self(bits)
+    }
+}
+
+impl From<GlobalCapabilities> for CapabilitiesSet {
+    fn from(capability: GlobalCapabilities) -> Self {
+        Self(capability as u64)
+    }
+}
+
+// names are separated with `|`, capabilities of disabled features are written by name,
+// other unknown bits are written as hex number, empty set is written as CapNone
+impl fmt::Display for CapabilitiesSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.0 == 0 {
+            return write!(f, "{}", GlobalCapabilities::CapNone)
+        }
+        let mut parts = self.iter().map(|cap| cap.name().to_string()).collect::<Vec<_>>();
+        let mut unknown = self.unknown_bits();
+        for (bit, name, _) in &FEATURE_GATED_CAPABILITIES {
+            if unknown & bit != 0 {
+                parts.push(name.to_string());
+                unknown &= !bit;
+            }
+        }
+        if unknown != 0 {
+            parts.push(format!("0x{:x}", unknown));
+        }
+        write!(f, "{}", parts.join("|"))
+    }
+}
+
+impl std::str::FromStr for CapabilitiesSet {
+    type Err = anyhow::Error;
+
+    fn from_str(string: &str) -> Result<Self> {
+        let mut bits = 0;
+        for part in string.split('|').map(str::trim).filter(|part| !part.is_empty()) {
+            if let Some(hex) = part.strip_prefix("0x") {
+                bits |= u64::from_str_radix(hex, 16)?;
+            } else if let Some(cap) = GlobalCapabilities::from_name(part) {
+                bits |= cap as u64;
+            } else if let Some((bit, _, _)) = FEATURE_GATED_CAPABILITIES.iter().find(|(_, name, _)| *name == part) {
+                bits |= bit;
+            } else {
+                fail!(BlockError::InvalidArg(format!("unknown capability `{}`", part)))
+            }
+        }
+        Ok(Self(bits))
+    }
+}
+
 impl ConfigParams {
     pub fn get_lt_align(&self) -> u64 {
         1_000_000
@@ -419,6 +740,9 @@ impl ConfigParams {
             Err(_) => 0
         }
     }
+    pub fn capabilities_set(&self) -> CapabilitiesSet {
+        CapabilitiesSet::with_bits(self.capabilities())
+    }
     pub fn global_version(&self) -> u32 {
         self.get_global_version().map_or(0, |gb| gb.version)
     }
@@ -500,6 +824,178 @@ impl ConfigParams {
     }
 }
 
+///
+/// Violation of config consistency rules
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigViolation {
+    /// index of the parameter, None for violations of the whole config
+    pub index: Option<u32>,
+    pub message: String,
+}
+
+impl ConfigViolation {
+    fn param(index: u32, message: String) -> Self {
+        Self { index: Some(index), message }
+    }
+}
+
+impl fmt::Display for ConfigViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.index {
+            Some(index) => write!(f, "config param {}: {}", index, self.message),
+            None => write!(f, "config: {}", self.message)
+        }
+    }
+}
+
+impl ConfigParams {
+    /// Check presence of mandatory params and internal consistency of known params.
+    /// Returns list of found violations, empty list means config is valid
+    pub fn config_violations(&self, relax_par0: bool, mparams: Option<MandatoryParams>) -> Result<Vec<ConfigViolation>> {
+        let mut violations = Vec::new();
+        if !relax_par0 {
+            match self.config(0) {
+                Ok(Some(ConfigParamEnum::ConfigParam0(param))) if param.config_addr == self.config_addr => (),
+                _ => violations.push(ConfigViolation::param(
+                    0, "config address is missing or differs from config smart contract address".to_string()
+                ))
+            }
+        }
+        let mut mandatory = MANDATORY_CONFIG_PARAMS.to_vec();
+        match self.config(9) {
+            Ok(Some(ConfigParamEnum::ConfigParam9(param))) => mandatory.extend(param.mandatory_params.export_keys::<u32>()?),
+            _ => violations.push(ConfigViolation::param(9, "mandatory params dictionary is missing or invalid".to_string()))
+        }
+        if let Some(mparams) = mparams {
+            mandatory.extend(mparams.export_keys::<u32>()?);
+        }
+        mandatory.sort_unstable();
+        mandatory.dedup();
+        for index in mandatory {
+            if !self.config_present(index)? {
+                violations.push(ConfigViolation::param(index, "mandatory param is missing".to_string()));
+            }
+        }
+
+        let mut indices = Vec::new();
+        self.config_params.iterate_slices(|mut key, _| {
+            indices.push(key.get_next_u32()?);
+            Ok(true)
+        })?;
+        for index in indices {
+            match self.config(index) {
+                Ok(Some(param)) => Self::check_param(index, &param, &mut violations),
+                Ok(None) => violations.push(ConfigViolation::param(index, "param has no value".to_string())),
+                Err(err) => violations.push(ConfigViolation::param(index, format!("cannot parse param: {}", err)))
+            }
+        }
+        Ok(violations)
+    }
+
+    fn check_param(index: u32, param: &ConfigParamEnum, violations: &mut Vec<ConfigViolation>) {
+        let mut violation = |message: String| violations.push(ConfigViolation::param(index, message));
+        match param {
+            ConfigParamEnum::ConfigParam12(param) => {
+                let result = param.workchains.iterate_with_keys(|workchain_id: i32, descr: WorkchainDescr| {
+                    if descr.min_split() > descr.max_split() || descr.max_split() > crate::shard::MAX_SPLIT_DEPTH {
+                        violation(format!("workchain {}: should be min_split <= max_split <= {}, but {} and {}",
+                            workchain_id, crate::shard::MAX_SPLIT_DEPTH, descr.min_split(), descr.max_split()));
+                    }
+                    if descr.actual_min_split() > descr.min_split() {
+                        violation(format!("workchain {}: actual_min_split {} is bigger than min_split {}",
+                            workchain_id, descr.actual_min_split(), descr.min_split()));
+                    }
+                    Ok(true)
+                });
+                if let Err(err) = result {
+                    violation(format!("cannot read workchains: {}", err));
+                }
+            }
+            ConfigParamEnum::ConfigParam16(param) => {
+                if param.min_validators < 1 {
+                    violation("min_validators should be at least 1".to_string());
+                }
+                if param.max_main_validators < param.min_validators.as_u32() {
+                    violation(format!("max_main_validators {} is less than min_validators {}",
+                        param.max_main_validators.as_u32(), param.min_validators.as_u32()));
+                }
+                if param.max_validators < param.max_main_validators.as_u32() {
+                    violation(format!("max_validators {} is less than max_main_validators {}",
+                        param.max_validators.as_u32(), param.max_main_validators.as_u32()));
+                }
+            }
+            ConfigParamEnum::ConfigParam17(param) => {
+                if param.min_stake > param.max_stake {
+                    violation(format!("min_stake {} is bigger than max_stake {}", param.min_stake, param.max_stake));
+                }
+            }
+            ConfigParamEnum::ConfigParam20(prices) |
+            ConfigParamEnum::ConfigParam21(prices) => Self::check_gas_prices(prices, &mut violation),
+            ConfigParamEnum::ConfigParam22(limits) |
+            ConfigParamEnum::ConfigParam23(limits) => {
+                let params = [("bytes", limits.bytes()), ("gas", limits.gas()), ("lt_delta", limits.lt_delta())];
+                for (name, limits) in params {
+                    if limits.underload() > limits.soft_limit() || limits.soft_limit() > limits.hard_limit() {
+                        violation(format!("{} limits should be underload <= soft <= hard, but {} {} {}",
+                            name, limits.underload(), limits.soft_limit(), limits.hard_limit()));
+                    }
+                }
+            }
+            ConfigParamEnum::ConfigParam32(ConfigParam32 { prev_validators: vset }) |
+            ConfigParamEnum::ConfigParam33(ConfigParam33 { prev_temp_validators: vset }) |
+            ConfigParamEnum::ConfigParam34(ConfigParam34 { cur_validators: vset }) |
+            ConfigParamEnum::ConfigParam35(ConfigParam35 { cur_temp_validators: vset }) |
+            ConfigParamEnum::ConfigParam36(ConfigParam36 { next_validators: vset }) |
+            ConfigParamEnum::ConfigParam37(ConfigParam37 { next_temp_validators: vset }) => {
+                Self::check_validator_set(vset, &mut violation)
+            }
+            _ => ()
+        }
+    }
+
+    fn check_gas_prices(prices: &GasLimitsPrices, violation: &mut impl FnMut(String)) {
+        if prices.flat_gas_limit > prices.gas_limit {
+            violation(format!("flat_gas_limit {} is bigger than gas_limit {}", prices.flat_gas_limit, prices.gas_limit));
+        }
+        if prices.gas_credit > prices.gas_limit {
+            violation(format!("gas_credit {} is bigger than gas_limit {}", prices.gas_credit, prices.gas_limit));
+        }
+        if prices.gas_limit > prices.block_gas_limit {
+            violation(format!("gas_limit {} is bigger than block_gas_limit {}", prices.gas_limit, prices.block_gas_limit));
+        }
+        if prices.freeze_due_limit > prices.delete_due_limit {
+            violation(format!("freeze_due_limit {} is bigger than delete_due_limit {}",
+                prices.freeze_due_limit, prices.delete_due_limit));
+        }
+        if prices.gas_price == 0 && prices.gas_limit > prices.flat_gas_limit {
+            violation("gas_price is zero, but gas_limit is bigger than flat_gas_limit".to_string());
+        }
+    }
+
+    fn check_validator_set(vset: &ValidatorSet, violation: &mut impl FnMut(String)) {
+        if vset.utime_since() >= vset.utime_until() {
+            violation(format!("utime_since {} should be less than utime_until {}", vset.utime_since(), vset.utime_until()));
+        }
+        if vset.main() < 1 || vset.main() > vset.total() {
+            violation(format!("main {} should be between 1 and total {}", vset.main(), vset.total()));
+        }
+        if vset.total() as usize != vset.list().len() {
+            violation(format!("total {} differs from list length {}", vset.total(), vset.list().len()));
+        }
+        let mut total_weight = 0u64;
+        for (i, descr) in vset.list().iter().enumerate() {
+            if descr.weight == 0 {
+                violation(format!("validator {} has zero weight", i));
+            }
+            total_weight = total_weight.saturating_add(descr.weight);
+        }
+        if total_weight != vset.total_weight() {
+            violation(format!("total_weight {} differs from sum of weights {}", vset.total_weight(), total_weight));
+        }
+    }
+}
+
 impl Deserializable for ConfigParams {
     fn read_from(&mut self, cell: &mut SliceData) -> Result<()> {
         self.config_addr.read_from(cell)?;
@@ -539,12 +1035,15 @@ pub enum ConfigParamEnum {
     ConfigParam16(ConfigParam16),
     ConfigParam17(ConfigParam17),
     ConfigParam18(ConfigParam18),
+    ConfigParam19(ConfigParam19),
     ConfigParam20(GasLimitsPrices),
     ConfigParam21(GasLimitsPrices),
     ConfigParam22(ConfigParam22),
     ConfigParam23(ConfigParam23),
     ConfigParam24(MsgForwardPrices),
     ConfigParam25(MsgForwardPrices),
+    ConfigParam26(ConfigParam26),
+    ConfigParam27(ConfigParam27),
     ConfigParam28(CatchainConfig),
     ConfigParam29(ConfigParam29),
     ConfigParam30(DelectorParams),
@@ -555,10 +1054,21 @@ pub enum ConfigParamEnum {
     ConfigParam35(ConfigParam35),
     ConfigParam36(ConfigParam36),
     ConfigParam37(ConfigParam37),
+    ConfigParam38(ConfigParam38),
     ConfigParam39(ConfigParam39),
     ConfigParam40(ConfigParam40),
+    ConfigParam41(ConfigParam41),
     ConfigParam42(ConfigCopyleft),
+    ConfigParam43(SizeLimitsConfig),
     ConfigParam44(SuspendedAddresses),
+    ConfigParam45(PrecompiledContractsConfig),
+    ConfigParam71(OracleBridgeParams),
+    ConfigParam72(OracleBridgeParams),
+    ConfigParam73(OracleBridgeParams),
+    ConfigParam79(JettonBridgeParams),
+    ConfigParam80(JettonBridgeParams),
+    ConfigParam81(JettonBridgeParams),
+    ConfigParam82(JettonBridgeParams),
     ConfigParamAny(u32, SliceData),
 }
 
@@ -599,12 +1109,15 @@ impl ConfigParamEnum {
             16 => { read_config!(ConfigParam16, ConfigParam16, slice) },
             17 => { read_config!(ConfigParam17, ConfigParam17, slice) },
             18 => { read_config!(ConfigParam18, ConfigParam18, slice) },
+            19 => { read_config!(ConfigParam19, ConfigParam19, slice) },
             20 => { read_config!(ConfigParam20, GasLimitsPrices, slice) },
             21 => { read_config!(ConfigParam21, GasLimitsPrices, slice) },
             22 => { read_config!(ConfigParam22, ConfigParam22, slice) },
             23 => { read_config!(ConfigParam23, ConfigParam23, slice) },
             24 => { read_config!(ConfigParam24, MsgForwardPrices, slice) },
             25 => { read_config!(ConfigParam25, MsgForwardPrices, slice) },
+            26 => { read_config!(ConfigParam26, ConfigParam26, slice) },
+            27 => { read_config!(ConfigParam27, ConfigParam27, slice) },
             28 => { read_config!(ConfigParam28, CatchainConfig, slice) },
             29 => { read_config!(ConfigParam29, ConfigParam29, slice) },
             30 => { read_config!(ConfigParam30, DelectorParams, slice) },
@@ -615,10 +1128,27 @@ impl ConfigParamEnum {
             35 => { read_config!(ConfigParam35, ConfigParam35, slice) },
             36 => { read_config!(ConfigParam36, ConfigParam36, slice) },
             37 => { read_config!(ConfigParam37, ConfigParam37, slice) },
+            38 => { read_config!(ConfigParam38, ConfigParam38, slice) },
             39 => { read_config!(ConfigParam39, ConfigParam39, slice) },
             40 => { read_config!(ConfigParam40, ConfigParam40, slice) },
+            41 => { read_config!(ConfigParam41, ConfigParam41, slice) },
             42 => { read_config!(ConfigParam42, ConfigCopyleft, slice) },
+            // newer formats of size limits are kept as raw data
+            43 => match slice.clone().get_next_byte() {
+                Ok(SIZE_LIMITS_CONFIG_TAG_1 | SIZE_LIMITS_CONFIG_TAG_2) => {
+                    read_config!(ConfigParam43, SizeLimitsConfig, slice)
+                }
+                _ => Ok(ConfigParamEnum::ConfigParamAny(index, slice.clone())),
+            },
             44 => { read_config!(ConfigParam44, SuspendedAddresses, slice) },
+            45 => { read_config!(ConfigParam45, PrecompiledContractsConfig, slice) },
+            71 => { read_config!(ConfigParam71, OracleBridgeParams, slice) },
+            72 => { read_config!(ConfigParam72, OracleBridgeParams, slice) },
+            73 => { read_config!(ConfigParam73, OracleBridgeParams, slice) },
+            79 => { read_config!(ConfigParam79, JettonBridgeParams, slice) },
+            80 => { read_config!(ConfigParam80, JettonBridgeParams, slice) },
+            81 => { read_config!(ConfigParam81, JettonBridgeParams, slice) },
+            82 => { read_config!(ConfigParam82, JettonBridgeParams, slice) },
             index => Ok(ConfigParamEnum::ConfigParamAny(index, slice.clone())),
         }
     }
@@ -645,12 +1175,15 @@ impl ConfigParamEnum {
             ConfigParamEnum::ConfigParam16(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(16)},
             ConfigParamEnum::ConfigParam17(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(17)},
             ConfigParamEnum::ConfigParam18(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(18)},
+            ConfigParamEnum::ConfigParam19(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(19)},
             ConfigParamEnum::ConfigParam20(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(20)},
             ConfigParamEnum::ConfigParam21(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(21)},
             ConfigParamEnum::ConfigParam22(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(22)},
             ConfigParamEnum::ConfigParam23(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(23)},
             ConfigParamEnum::ConfigParam24(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(24)},
             ConfigParamEnum::ConfigParam25(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(25)},
+            ConfigParamEnum::ConfigParam26(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(26)},
+            ConfigParamEnum::ConfigParam27(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(27)},
             ConfigParamEnum::ConfigParam28(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(28)},
             ConfigParamEnum::ConfigParam29(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(29)},
             ConfigParamEnum::ConfigParam30(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(30)},
@@ -661,10 +1194,21 @@ impl ConfigParamEnum {
             ConfigParamEnum::ConfigParam35(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(35)},
             ConfigParamEnum::ConfigParam36(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(36)},
             ConfigParamEnum::ConfigParam37(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(37)},
+            ConfigParamEnum::ConfigParam38(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(38)},
             ConfigParamEnum::ConfigParam39(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(39)},
             ConfigParamEnum::ConfigParam40(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(40)},
+            ConfigParamEnum::ConfigParam41(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(41)},
             ConfigParamEnum::ConfigParam42(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(42)},
+            ConfigParamEnum::ConfigParam43(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(43)},
             ConfigParamEnum::ConfigParam44(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(44)},
+            ConfigParamEnum::ConfigParam45(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(45)},
+            ConfigParamEnum::ConfigParam71(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(71)},
+            ConfigParamEnum::ConfigParam72(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(72)},
+            ConfigParamEnum::ConfigParam73(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(73)},
+            ConfigParamEnum::ConfigParam79(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(79)},
+            ConfigParamEnum::ConfigParam80(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(80)},
+            ConfigParamEnum::ConfigParam81(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(81)},
+            ConfigParamEnum::ConfigParam82(ref c) => { cell.checked_append_reference(c.serialize()?)?; Ok(82)},
             ConfigParamEnum::ConfigParamAny(index, slice) => {
                 cell.checked_append_reference(slice.clone().into_cell())?;
                 Ok(*index)
@@ -924,6 +1468,9 @@ impl GlobalVersion {
     pub fn has_capability(&self, capability: GlobalCapabilities) -> bool {
         (self.capabilities & (capability as u64)) != 0
     }
+    pub fn capabilities_set(&self) -> CapabilitiesSet {
+        CapabilitiesSet::with_bits(self.capabilities)
+    }
 }
 
 const GLOBAL_VERSION_TAG: u8 = 0xC4;
@@ -1325,6 +1872,48 @@ impl ConfigParam18 {
         self.map.get(&index)?.ok_or_else(|| error!(BlockError::InvalidIndex(index as usize)))
     }
 
+    /// Calculate storage fee for the period from last_paid till now
+    pub fn calc_storage_fee(
+        &self,
+        used: &StorageUsed,
+        last_paid: u32,
+        now: u32,
+        is_masterchain: bool
+    ) -> Result<u128> {
+        let prices = self.map.export_vector()?;
+        match prices.first() {
+            Some(first) if now > last_paid && last_paid != 0 && now > first.utime_since => (),
+            _ => return Ok(0)
+        }
+        let bits = used.bits() as u128;
+        let cells = used.cells() as u128;
+        let mut upto = std::cmp::max(last_paid, prices[0].utime_since);
+        let mut fee = 0u128;
+        // prices are sorted by utime_since, each of them is valid till the next one begins
+        // (but not later than now, next prices may be scheduled for the future)
+        for (i, price) in prices.iter().enumerate() {
+            if upto >= now {
+                break
+            }
+            let valid_until = match prices.get(i + 1) {
+                Some(next) => std::cmp::min(next.utime_since, now),
+                None => now
+            };
+            if upto < valid_until {
+                let delta = valid_until - upto;
+                let (bit_price, cell_price) = if is_masterchain {
+                    (price.mc_bit_price_ps, price.mc_cell_price_ps)
+                } else {
+                    (price.bit_price_ps, price.cell_price_ps)
+                };
+                fee += (cells * cell_price as u128 + bits * bit_price as u128) * delta as u128;
+            }
+            upto = std::cmp::max(upto, valid_until);
+        }
+        // prices are stored as fixed point values shifted by 16 bits
+        Ok((fee + 0xffff) >> 16)
+    }
+
     /// insert value
     pub fn insert(&mut self, sp: &StoragePrices) -> Result<()> {
         let index = match self.map.0.get_max(false, &mut 0)? {
@@ -1353,6 +1942,38 @@ impl Serializable for ConfigParam18 {
     }
 }
 
+/*
+_ global_id:int32 = ConfigParam 19;
+*/
+
+///
+/// Config Param 19 structure
+///
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct ConfigParam19 {
+    pub global_id: i32,
+}
+
+impl ConfigParam19 {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Deserializable for ConfigParam19 {
+    fn read_from(&mut self, cell: &mut SliceData) -> Result<()> {
+        self.global_id.read_from(cell)?;
+        Ok(())
+    }
+}
+
+impl Serializable for ConfigParam19 {
+    fn write_to(&self, cell: &mut BuilderData) -> Result<()> {
+        self.global_id.write_to(cell)?;
+        Ok(())
+    }
+}
+
 /*
 gas_prices#dd
     gas_price:uint64
@@ -1551,6 +2172,39 @@ impl MsgForwardPrices {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Calculate forward fee for serialized message
+    /// bits and cells of the root cell are not counted (lump_price pays for them)
+    pub fn fwd_fee(&self, msg_cell: &Cell) -> u128 {
+        let mut storage = StorageUsedShort::default();
+        storage.append(msg_cell);
+        let bits = storage.bits() - msg_cell.bit_length() as u64;
+        let cells = storage.cells() - 1;
+        self.fwd_fee_for_stat(cells, bits)
+    }
+
+    /// Calculate forward fee by message cells and bits count (root cell excluded)
+    pub fn fwd_fee_for_stat(&self, cells: u64, bits: u64) -> u128 {
+        // cell_price and bit_price are pseudo values (shifted by 16)
+        // after calculation divide by 0xffff with ceil rounding
+        self.lump_price as u128
+            + ((cells as u128 * self.cell_price as u128 + bits as u128 * self.bit_price as u128 + 0xffff) >> 16)
+    }
+
+    /// Calculate IHR fee by forward fee
+    pub fn ihr_fee(&self, fwd_fee: u128) -> u128 {
+        (fwd_fee * self.ihr_price_factor as u128 + 0xffff) >> 16
+    }
+
+    /// Part of forward fee collected by the block where message was created
+    pub fn mine_fee(&self, fwd_fee: u128) -> u128 {
+        (fwd_fee * self.first_frac as u128) >> 16
+    }
+
+    /// Part of remaining forward fee collected by each transit block
+    pub fn next_fee(&self, fwd_fee: u128) -> u128 {
+        (fwd_fee * self.next_frac as u128) >> 16
+    }
 }
 
 const MSG_FWD_PRICES_TAG: u8 = 0xEA;
@@ -2218,6 +2872,19 @@ impl WorkchainFormat0 {
             )
         }
     }
+
+    ///
+    /// Check if address of addr_len bits is allowed in the workchain:
+    /// it must be between min_addr_len and max_addr_len and differ from
+    /// min_addr_len by a multiple of addr_len_step
+    ///
+    pub fn is_valid_addr_len(&self, addr_len: u16) -> bool {
+        let min = self.min_addr_len();
+        let max = self.max_addr_len();
+        let step = self.addr_len_step();
+        (min..=max).contains(&addr_len)
+            && (addr_len == min || addr_len == max || (step > 0 && (addr_len - min) % step == 0))
+    }
 }
 
 impl Deserializable for WorkchainFormat0 {
@@ -2343,6 +3010,22 @@ impl WorkchainDescr {
         self.actual_min_split
     }
 
+    ///
+    /// Setter for actual_min_split
+    ///
+    pub fn set_actual_min_split(&mut self, actual_min_split: u8) -> Result<()> {
+        if actual_min_split <= crate::shard::MAX_SPLIT_DEPTH {
+            self.actual_min_split = actual_min_split;
+            Ok(())
+        } else {
+            fail!(
+                BlockError::InvalidData(
+                    "should: actual_min_split <= 60".to_string()
+                )
+            )
+        }
+    }
+
     ///
     /// Getter for max_split
     ///
@@ -2374,6 +3057,59 @@ impl WorkchainDescr {
         matches!(self.format, WorkchainFormat::Basic(_))
     }
 
+    ///
+    /// Check if address of addr_len bits is allowed by the workchain format.
+    /// Basic workchains use only 256-bit addresses.
+    ///
+    pub fn is_valid_addr_len(&self, addr_len: usize) -> bool {
+        match &self.format {
+            WorkchainFormat::Basic(_) => addr_len == 256,
+            WorkchainFormat::Extended(format) => {
+                u16::try_from(addr_len).map(|len| format.is_valid_addr_len(len)).unwrap_or(false)
+            }
+        }
+    }
+
+    ///
+    /// Check the address against the workchain: the workchain must be active,
+    /// standard address is allowed only if the workchain accepts 256-bit addresses,
+    /// variable address is used only in extended workchains with the length
+    /// fitting the address format, anycast depth must fit the split limits
+    ///
+    pub fn check_address(&self, address: &MsgAddressInt) -> Result<()> {
+        let workchain_id = address.workchain_id();
+        if !self.active {
+            fail!(BlockError::InvalidArg(format!("workchain {} is not active", workchain_id)))
+        }
+        match address {
+            MsgAddressInt::AddrStd(addr) => {
+                if !self.is_valid_addr_len(addr.address.remaining_bits()) {
+                    fail!(BlockError::InvalidArg(format!(
+                        "standard address is not allowed in workchain {}", workchain_id
+                    )))
+                }
+            }
+            MsgAddressInt::AddrVar(addr) => {
+                let addr_len = addr.address.remaining_bits();
+                if addr_len != addr.addr_len.as_usize() {
+                    fail!(BlockError::InvalidArg(format!(
+                        "address length {} doesn't match addr_len {}", addr_len, addr.addr_len
+                    )))
+                }
+                if self.basic() {
+                    fail!(BlockError::InvalidArg(format!(
+                        "variable address is not allowed in basic workchain {}", workchain_id
+                    )))
+                }
+                if !self.is_valid_addr_len(addr_len) {
+                    fail!(BlockError::InvalidArg(format!(
+                        "address length {} is not allowed in workchain {}", addr_len, workchain_id
+                    )))
+                }
+            }
+        }
+        address.check_anycast_for_workchain(self)
+    }
 }
 
 const WORKCHAIN_DESCRIPTOR_TAG : u8 = 0xA6;
@@ -2684,6 +3420,52 @@ impl Serializable for ConfigParam13 {
     }
 }
 
+// Reserved parameters 26, 27, 38 and 41: the scheme doesn't fix their layout,
+// so the data of the parameter is kept as is and written back unchanged
+macro_rules! define_reserved_configparam {
+    ( $cpname:ident, $index:literal ) => {
+        ///
+        #[doc = concat!("ConfigParam ", stringify!($index), " struct (reserved, raw data of the parameter)")]
+        ///
+        #[derive(Clone, Debug, Default, Eq, PartialEq)]
+        pub struct $cpname {
+            pub cell: Cell,
+        }
+
+        impl $cpname {
+            pub fn with_cell(cell: Cell) -> Self {
+                Self { cell }
+            }
+        }
+
+        impl Deserializable for $cpname {
+            fn read_from(&mut self, slice: &mut SliceData) -> Result<()> {
+                // parameter is the rest of the slice from its current position
+                let mut builder = BuilderData::new();
+                builder.checked_append_references_and_data(slice)?;
+                self.cell = builder.into_cell()?;
+                slice.get_next_bits(slice.remaining_bits())?;
+                while slice.remaining_references() != 0 {
+                    slice.checked_drain_reference()?;
+                }
+                Ok(())
+            }
+        }
+
+        impl Serializable for $cpname {
+            fn write_to(&self, cell: &mut BuilderData) -> Result<()> {
+                cell.checked_append_references_and_data(&SliceData::load_cell_ref(&self.cell)?)?;
+                Ok(())
+            }
+        }
+    }
+}
+
+define_reserved_configparam!(ConfigParam26, 26);
+define_reserved_configparam!(ConfigParam27, 27);
+define_reserved_configparam!(ConfigParam38, 38);
+define_reserved_configparam!(ConfigParam41, 41);
+
 // validator_temp_key#3
 //     adnl_addr:bits256
 //     temp_public_key:SigPubKey
@@ -3257,6 +4039,117 @@ impl Serializable for ConfigCopyleft {
     }
 }
 
+/*
+size_limits_config#01
+    max_msg_bits:uint32
+    max_msg_cells:uint32
+    max_library_cells:uint32
+    max_vm_data_depth:uint16
+    max_ext_msg_size:uint32
+    max_ext_msg_depth:uint16
+= SizeLimitsConfig;
+
+size_limits_config_v2#02
+    max_msg_bits:uint32
+    max_msg_cells:uint32
+    max_library_cells:uint32
+    max_vm_data_depth:uint16
+    max_ext_msg_size:uint32
+    max_ext_msg_depth:uint16
+    max_acc_state_cells:uint32
+    max_acc_state_bits:uint32
+    max_acc_public_libraries:uint32
+    defer_out_queue_size_limit:uint32
+= SizeLimitsConfig;
+
+_ SizeLimitsConfig = ConfigParam 43;
+*/
+
+const SIZE_LIMITS_CONFIG_TAG_1: u8 = 0x01;
+const SIZE_LIMITS_CONFIG_TAG_2: u8 = 0x02;
+
+///
+/// ConfigParam 43 struct
+///
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct SizeLimitsConfig {
+    pub max_msg_bits: u32,
+    pub max_msg_cells: u32,
+    pub max_library_cells: u32,
+    pub max_vm_data_depth: u16,
+    pub max_ext_msg_size: u32,
+    pub max_ext_msg_depth: u16,
+    /// fields of v2 format, None for v1
+    pub acc_state_limits: Option<SizeLimitsAccState>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct SizeLimitsAccState {
+    pub max_acc_state_cells: u32,
+    pub max_acc_state_bits: u32,
+    pub max_acc_public_libraries: u32,
+    pub defer_out_queue_size_limit: u32,
+}
+
+impl SizeLimitsConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Deserializable for SizeLimitsConfig {
+    fn read_from(&mut self, cell: &mut SliceData) -> Result<()> {
+        let tag = cell.get_next_byte()?;
+        if tag != SIZE_LIMITS_CONFIG_TAG_1 && tag != SIZE_LIMITS_CONFIG_TAG_2 {
+            fail!(
+                BlockError::InvalidConstructorTag {
+                    t: tag as u32,
+                    s: std::any::type_name::<Self>().to_string()
+                }
+            )
+        }
+        self.max_msg_bits.read_from(cell)?;
+        self.max_msg_cells.read_from(cell)?;
+        self.max_library_cells.read_from(cell)?;
+        self.max_vm_data_depth.read_from(cell)?;
+        self.max_ext_msg_size.read_from(cell)?;
+        self.max_ext_msg_depth.read_from(cell)?;
+        self.acc_state_limits = if tag == SIZE_LIMITS_CONFIG_TAG_2 {
+            let mut limits = SizeLimitsAccState::default();
+            limits.max_acc_state_cells.read_from(cell)?;
+            limits.max_acc_state_bits.read_from(cell)?;
+            limits.max_acc_public_libraries.read_from(cell)?;
+            limits.defer_out_queue_size_limit.read_from(cell)?;
+            Some(limits)
+        } else {
+            None
+        };
+        Ok(())
+    }
+}
+
+impl Serializable for SizeLimitsConfig {
+    fn write_to(&self, cell: &mut BuilderData) -> Result<()> {
+        match self.acc_state_limits {
+            Some(_) => cell.append_u8(SIZE_LIMITS_CONFIG_TAG_2)?,
+            None => cell.append_u8(SIZE_LIMITS_CONFIG_TAG_1)?,
+        };
+        self.max_msg_bits.write_to(cell)?;
+        self.max_msg_cells.write_to(cell)?;
+        self.max_library_cells.write_to(cell)?;
+        self.max_vm_data_depth.write_to(cell)?;
+        self.max_ext_msg_size.write_to(cell)?;
+        self.max_ext_msg_depth.write_to(cell)?;
+        if let Some(limits) = &self.acc_state_limits {
+            limits.max_acc_state_cells.write_to(cell)?;
+            limits.max_acc_state_bits.write_to(cell)?;
+            limits.max_acc_public_libraries.write_to(cell)?;
+            limits.defer_out_queue_size_limit.write_to(cell)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Default)]
 pub struct SuspendedAddressesKey {
     pub workchain_id: i32,
@@ -3292,3 +4185,335 @@ impl SuspendedAddresses {
         self.set(&key, &())
     }
 }
+
+/*
+precompiled_smc#b0 gas_usage:uint64 = PrecompiledSmc;
+precompiled_contracts_config#c0 list:(HashmapE 256 PrecompiledSmc) = PrecompiledContractsConfig;
+_ PrecompiledContractsConfig = ConfigParam 45;
+*/
+
+const PRECOMPILED_SMC_TAG: u8 = 0xB0;
+
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct PrecompiledSmc {
+    pub gas_usage: u64,
+}
+
+impl PrecompiledSmc {
+    pub fn with_gas_usage(gas_usage: u64) -> Self {
+        Self { gas_usage }
+    }
+}
+
+impl Deserializable for PrecompiledSmc {
+    fn read_from(&mut self, cell: &mut SliceData) -> Result<()> {
+        let tag = cell.get_next_byte()?;
+        if tag != PRECOMPILED_SMC_TAG {
+            fail!(
+                BlockError::InvalidConstructorTag {
+                    t: tag as u32,
+                    s: std::any::type_name::<Self>().to_string()
+                }
+            )
+        }
+        self.gas_usage.read_from(cell)?;
+        Ok(())
+    }
+}
+
+impl Serializable for PrecompiledSmc {
+    fn write_to(&self, cell: &mut BuilderData) -> Result<()> {
+        cell.append_u8(PRECOMPILED_SMC_TAG)?;
+        self.gas_usage.write_to(cell)?;
+        Ok(())
+    }
+}
+
+define_HashmapE!{PrecompiledContracts, 256, PrecompiledSmc}
+
+const PRECOMPILED_CONTRACTS_CONFIG_TAG: u8 = 0xC0;
+
+///
+/// ConfigParam 45 struct
+///
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct PrecompiledContractsConfig {
+    pub list: PrecompiledContracts,
+}
+
+impl PrecompiledContractsConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// get precompiled contract description by code hash
+    pub fn get(&self, code_hash: &UInt256) -> Result<Option<PrecompiledSmc>> {
+        self.list.get(code_hash)
+    }
+
+    /// insert precompiled contract description
+    pub fn insert(&mut self, code_hash: &UInt256, smc: &PrecompiledSmc) -> Result<()> {
+        self.list.set(code_hash, smc)
+    }
+}
+
+impl Deserializable for PrecompiledContractsConfig {
+    fn read_from(&mut self, cell: &mut SliceData) -> Result<()> {
+        let tag = cell.get_next_byte()?;
+        if tag != PRECOMPILED_CONTRACTS_CONFIG_TAG {
+            fail!(
+                BlockError::InvalidConstructorTag {
+                    t: tag as u32,
+                    s: std::any::type_name::<Self>().to_string()
+                }
+            )
+        }
+        self.list.read_from(cell)?;
+        Ok(())
+    }
+}
+
+impl Serializable for PrecompiledContractsConfig {
+    fn write_to(&self, cell: &mut BuilderData) -> Result<()> {
+        cell.append_u8(PRECOMPILED_CONTRACTS_CONFIG_TAG)?;
+        self.list.write_to(cell)?;
+        Ok(())
+    }
+}
+
+define_HashmapE!{BridgeOracles, 256, UInt256}
+
+/*
+oracle_bridge_params#_
+    bridge_address:bits256
+    oracle_mutlisig_address:bits256
+    oracles:(HashmapE 256 uint256)
+    external_chain_address:bits256
+= OracleBridgeParams;
+
+_ OracleBridgeParams = ConfigParam 71; // Ethereum bridge
+_ OracleBridgeParams = ConfigParam 72; // Binance Smart Chain bridge
+_ OracleBridgeParams = ConfigParam 73; // Polygon bridge
+*/
+
+///
+/// ConfigParam 71-73 struct
+///
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct OracleBridgeParams {
+    pub bridge_address: UInt256,
+    pub oracle_multisig_address: UInt256,
+    pub oracles: BridgeOracles,
+    pub external_chain_address: UInt256,
+}
+
+impl OracleBridgeParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Deserializable for OracleBridgeParams {
+    fn read_from(&mut self, cell: &mut SliceData) -> Result<()> {
+        self.bridge_address.read_from(cell)?;
+        self.oracle_multisig_address.read_from(cell)?;
+        self.oracles.read_from(cell)?;
+        self.external_chain_address.read_from(cell)?;
+        Ok(())
+    }
+}
+
+impl Serializable for OracleBridgeParams {
+    fn write_to(&self, cell: &mut BuilderData) -> Result<()> {
+        self.bridge_address.write_to(cell)?;
+        self.oracle_multisig_address.write_to(cell)?;
+        self.oracles.write_to(cell)?;
+        self.external_chain_address.write_to(cell)?;
+        Ok(())
+    }
+}
+
+/*
+jetton_bridge_prices#_
+    bridge_burn_fee:Coins
+    bridge_mint_fee:Coins
+    wallet_min_tons_for_storage:Coins
+    wallet_gas_consumption:Coins
+    minter_min_tons_for_storage:Coins
+    discover_gas_consumption:Coins
+= JettonBridgePrices;
+*/
+
+///
+/// Prices of the token bridge operations
+///
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct JettonBridgePrices {
+    pub bridge_burn_fee: Grams,
+    pub bridge_mint_fee: Grams,
+    pub wallet_min_tons_for_storage: Grams,
+    pub wallet_gas_consumption: Grams,
+    pub minter_min_tons_for_storage: Grams,
+    pub discover_gas_consumption: Grams,
+}
+
+impl Deserializable for JettonBridgePrices {
+    fn read_from(&mut self, cell: &mut SliceData) -> Result<()> {
+        self.bridge_burn_fee.read_from(cell)?;
+        self.bridge_mint_fee.read_from(cell)?;
+        self.wallet_min_tons_for_storage.read_from(cell)?;
+        self.wallet_gas_consumption.read_from(cell)?;
+        self.minter_min_tons_for_storage.read_from(cell)?;
+        self.discover_gas_consumption.read_from(cell)?;
+        Ok(())
+    }
+}
+
+impl Serializable for JettonBridgePrices {
+    fn write_to(&self, cell: &mut BuilderData) -> Result<()> {
+        self.bridge_burn_fee.write_to(cell)?;
+        self.bridge_mint_fee.write_to(cell)?;
+        self.wallet_min_tons_for_storage.write_to(cell)?;
+        self.wallet_gas_consumption.write_to(cell)?;
+        self.minter_min_tons_for_storage.write_to(cell)?;
+        self.discover_gas_consumption.write_to(cell)?;
+        Ok(())
+    }
+}
+
+/*
+jetton_bridge_params_v0#00
+    bridge_address:bits256
+    oracles_address:bits256
+    oracles:(HashmapE 256 uint256)
+    state_flags:uint8
+    burn_bridge_fee:Coins
+= JettonBridgeParams;
+
+jetton_bridge_params_v1#01
+    bridge_address:bits256
+    oracles_address:bits256
+    oracles:(HashmapE 256 uint256)
+    state_flags:uint8
+    prices:^JettonBridgePrices
+    external_chain_address:bits256
+= JettonBridgeParams;
+
+_ JettonBridgeParams = ConfigParam 79; // ETH->TON token bridge
+_ JettonBridgeParams = ConfigParam 80;
+_ JettonBridgeParams = ConfigParam 81; // BNB->TON token bridge
+_ JettonBridgeParams = ConfigParam 82; // Polygon->TON token bridge
+*/
+
+const JETTON_BRIDGE_PARAMS_V0_TAG: u8 = 0x00;
+const JETTON_BRIDGE_PARAMS_V1_TAG: u8 = 0x01;
+
+///
+/// Version specific part of the token bridge params
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum JettonBridgeFees {
+    /// only flat fee for burning tokens
+    V0 {
+        burn_bridge_fee: Grams,
+    },
+    /// full prices (including minter storage) and bridge address in external chain
+    V1 {
+        prices: JettonBridgePrices,
+        external_chain_address: UInt256,
+    },
+}
+
+impl Default for JettonBridgeFees {
+    fn default() -> Self {
+        JettonBridgeFees::V0 { burn_bridge_fee: Grams::zero() }
+    }
+}
+
+///
+/// ConfigParam 79-82 struct
+///
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct JettonBridgeParams {
+    pub bridge_address: UInt256,
+    pub oracles_address: UInt256,
+    pub oracles: BridgeOracles,
+    pub state_flags: u8,
+    pub fees: JettonBridgeFees,
+}
+
+impl JettonBridgeParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn prices(&self) -> Option<&JettonBridgePrices> {
+        match &self.fees {
+            JettonBridgeFees::V1 { prices, .. } => Some(prices),
+            JettonBridgeFees::V0 { .. } => None
+        }
+    }
+
+    pub fn burn_bridge_fee(&self) -> &Grams {
+        match &self.fees {
+            JettonBridgeFees::V0 { burn_bridge_fee } => burn_bridge_fee,
+            JettonBridgeFees::V1 { prices, .. } => &prices.bridge_burn_fee
+        }
+    }
+}
+
+impl Deserializable for JettonBridgeParams {
+    fn read_from(&mut self, cell: &mut SliceData) -> Result<()> {
+        let tag = cell.get_next_byte()?;
+        if tag != JETTON_BRIDGE_PARAMS_V0_TAG && tag != JETTON_BRIDGE_PARAMS_V1_TAG {
+            fail!(
+                BlockError::InvalidConstructorTag {
+                    t: tag as u32,
+                    s: std::any::type_name::<Self>().to_string()
+                }
+            )
+        }
+        self.bridge_address.read_from(cell)?;
+        self.oracles_address.read_from(cell)?;
+        self.oracles.read_from(cell)?;
+        self.state_flags.read_from(cell)?;
+        self.fees = if tag == JETTON_BRIDGE_PARAMS_V0_TAG {
+            JettonBridgeFees::V0 {
+                burn_bridge_fee: Grams::construct_from(cell)?,
+            }
+        } else {
+            JettonBridgeFees::V1 {
+                prices: JettonBridgePrices::construct_from_reference(cell)?,
+                external_chain_address: UInt256::construct_from(cell)?,
+            }
+        };
+        Ok(())
+    }
+}
+
+impl Serializable for JettonBridgeParams {
+    fn write_to(&self, cell: &mut BuilderData) -> Result<()> {
+        match self.fees {
+            JettonBridgeFees::V0 { .. } => cell.append_u8(JETTON_BRIDGE_PARAMS_V0_TAG)?,
+            JettonBridgeFees::V1 { .. } => cell.append_u8(JETTON_BRIDGE_PARAMS_V1_TAG)?,
+        };
+        self.bridge_address.write_to(cell)?;
+        self.oracles_address.write_to(cell)?;
+        self.oracles.write_to(cell)?;
+        self.state_flags.write_to(cell)?;
+        match &self.fees {
+            JettonBridgeFees::V0 { burn_bridge_fee } => {
+                burn_bridge_fee.write_to(cell)?;
+            }
+            JettonBridgeFees::V1 { prices, external_chain_address } => {
+                cell.checked_append_reference(prices.serialize()?)?;
+                external_chain_address.write_to(cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/test_config_params.rs"]
+mod tests;
@@ -0,0 +1,301 @@
+/*
+* Copyright 2018-2020 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use ton_types::{error, fail, BuilderData, Cell, HashmapType, IBitstring, Result, SliceData, UInt256};
+
+use crate::{
+    config_params::{ConfigParamEnum, ConfigParams, ConfigProposalSetup},
+    error::BlockError,
+    signature::SigPubKey,
+    validators::ValidatorSet,
+    Deserializable, Serializable,
+};
+
+/*
+cfg_proposal#f3
+    param_id:int32
+    param_value:(Maybe ^Cell)
+    if_hash_equal:(Maybe uint256)
+= ConfigProposal;
+*/
+
+const CONFIG_PROPOSAL_TAG: u8 = 0xF3;
+
+///
+/// Proposal to change one config parameter (as it is sent to config smart contract)
+/// param_value None means removing of the parameter
+///
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ConfigProposal {
+    pub param_id: i32,
+    pub param_value: Option<Cell>,
+    pub if_hash_equal: Option<UInt256>,
+}
+
+impl ConfigProposal {
+    /// Proposal to set the parameter to the given value
+    pub fn with_param(param: &ConfigParamEnum) -> Result<Self> {
+        let mut cell = BuilderData::new();
+        let index = param.write_to_cell(&mut cell)?;
+        Ok(Self {
+            param_id: index as i32,
+            param_value: Some(cell.into_cell()?.reference(0)?),
+            if_hash_equal: None,
+        })
+    }
+
+    /// Proposal to remove the parameter
+    pub fn with_removal(index: u32) -> Self {
+        Self {
+            param_id: index as i32,
+            param_value: None,
+            if_hash_equal: None,
+        }
+    }
+
+    /// Make proposal applicable only if current value of the parameter is not changed
+    pub fn with_hash_check(mut self, config: &ConfigParams) -> Result<Self> {
+        let key = SliceData::load_builder((self.param_id as u32).write_to_new_cell()?)?;
+        self.if_hash_equal = match config.config_params.get(key)? {
+            Some(slice) => slice.reference_opt(0).map(|cell| cell.repr_hash()),
+            None => None
+        };
+        Ok(self)
+    }
+
+    pub fn param_index(&self) -> u32 {
+        self.param_id as u32
+    }
+
+    /// Proposal is critical if parameter is listed in ConfigParam 10
+    pub fn is_critical(&self, config: &ConfigParams) -> Result<bool> {
+        match config.critical_params() {
+            Ok(critical_params) => critical_params.check_key(&self.param_index()),
+            Err(_) => Ok(false)
+        }
+    }
+
+    /// Voting rules applied to the proposal
+    pub fn voting_setup(&self, config: &ConfigParams) -> Result<ConfigProposalSetup> {
+        let voting_setup = config.config_voting_setup()?;
+        match self.is_critical(config)? {
+            true => voting_setup.read_critical_params(),
+            false => voting_setup.read_normal_params()
+        }
+    }
+
+    /// Price of storing proposal in config smart contract for ttl seconds
+    pub fn storage_price(&self, setup: &ConfigProposalSetup, ttl: u32) -> Result<u128> {
+        if ttl < setup.min_store_sec || ttl > setup.max_store_sec {
+            fail!(BlockError::InvalidArg(
+                format!("proposal ttl {} should be in range {}..={}", ttl, setup.min_store_sec, setup.max_store_sec)
+            ))
+        }
+        let (cells, bits) = match &self.param_value {
+            Some(cell) => {
+                let mut storage = crate::accounts::StorageUsedShort::default();
+                storage.append(cell);
+                (storage.cells() as u128, storage.bits() as u128)
+            }
+            None => (0, 0)
+        };
+        // the same formula is used by config smart contract
+        let pps = setup.bit_price as u128 * (bits + 1024) + setup.cell_price as u128 * (cells + 2);
+        Ok(pps * ttl as u128)
+    }
+}
+
+impl Deserializable for ConfigProposal {
+    fn read_from(&mut self, slice: &mut SliceData) -> Result<()> {
+        let tag = slice.get_next_byte()?;
+        if tag != CONFIG_PROPOSAL_TAG {
+            fail!(
+                BlockError::InvalidConstructorTag {
+                    t: tag as u32,
+                    s: std::any::type_name::<Self>().to_string()
+                }
+            )
+        }
+        self.param_id.read_from(slice)?;
+        self.param_value = match slice.get_next_bit()? {
+            true => Some(slice.checked_drain_reference()?),
+            false => None
+        };
+        self.if_hash_equal = UInt256::construct_maybe_from(slice)?;
+        Ok(())
+    }
+}
+
+impl Serializable for ConfigProposal {
+    fn write_to(&self, cell: &mut BuilderData) -> Result<()> {
+        cell.append_u8(CONFIG_PROPOSAL_TAG)?;
+        self.param_id.write_to(cell)?;
+        match &self.param_value {
+            Some(value) => {
+                cell.append_bit_one()?;
+                cell.checked_append_reference(value.clone())?;
+            }
+            None => {
+                cell.append_bit_zero()?;
+            }
+        }
+        match &self.if_hash_equal {
+            Some(hash) => {
+                cell.append_bit_one()?;
+                hash.write_to(cell)?;
+            }
+            None => {
+                cell.append_bit_zero()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProposalStatus {
+    /// voting is not finished
+    Pending,
+    /// proposal is accepted in round with given number (starting from 1)
+    Accepted(u8),
+    /// proposal is rejected in round with given number (starting from 1)
+    Rejected(u8),
+}
+
+///
+/// Offline simulator of voting for config proposal.
+/// Round is won if validators with more than 3/4 of total weight voted for the proposal.
+/// Proposal is accepted after min_wins won rounds (but not earlier than min_tot_rounds),
+/// and rejected after more than max_losses lost rounds or max_tot_rounds rounds.
+///
+#[derive(Clone, Debug)]
+pub struct ProposalVoteSimulator {
+    setup: ConfigProposalSetup,
+    critical: bool,
+    weights: Vec<u64>,
+    total_weight: u64,
+    rounds: u8,
+    wins: u8,
+    losses: u8,
+    status: ProposalStatus,
+}
+
+impl ProposalVoteSimulator {
+    pub fn new(setup: ConfigProposalSetup, critical: bool, vset: &ValidatorSet) -> Result<Self> {
+        if vset.list().is_empty() {
+            fail!(BlockError::InvalidArg("validator set is empty".to_string()))
+        }
+        let weights: Vec<u64> = vset.list().iter().map(|descr| descr.weight).collect();
+        let total_weight = weights.iter().fold(0u64, |sum, weight| sum.saturating_add(*weight));
+        Ok(Self {
+            setup,
+            critical,
+            weights,
+            total_weight,
+            rounds: 0,
+            wins: 0,
+            losses: 0,
+            status: ProposalStatus::Pending,
+        })
+    }
+
+    /// Create simulator for the proposal using voting setup and current validator set of the config
+    pub fn with_config(proposal: &ConfigProposal, config: &ConfigParams) -> Result<Self> {
+        Self::new(proposal.voting_setup(config)?, proposal.is_critical(config)?, &config.validator_set()?)
+    }
+
+    pub fn is_critical(&self) -> bool {
+        self.critical
+    }
+
+    pub fn setup(&self) -> &ConfigProposalSetup {
+        &self.setup
+    }
+
+    pub fn status(&self) -> ProposalStatus {
+        self.status
+    }
+
+    pub fn rounds(&self) -> u8 {
+        self.rounds
+    }
+
+    pub fn wins(&self) -> u8 {
+        self.wins
+    }
+
+    pub fn losses(&self) -> u8 {
+        self.losses
+    }
+
+    /// Weight of votes needed to win one round
+    pub fn round_threshold(&self) -> u64 {
+        ((self.total_weight as u128 * 3 / 4) + 1) as u64
+    }
+
+    /// Sum of weights of validators with given indexes, each validator is counted once
+    pub fn votes_weight(&self, voters: &[usize]) -> Result<u64> {
+        let mut counted = vec![false; self.weights.len()];
+        let mut weight = 0u64;
+        for index in voters {
+            match counted.get_mut(*index) {
+                Some(counted) if !*counted => {
+                    *counted = true;
+                    weight = weight.saturating_add(self.weights[*index]);
+                }
+                Some(_) => (),
+                None => fail!(BlockError::InvalidIndex(*index))
+            }
+        }
+        Ok(weight)
+    }
+
+    /// Simulate one voting round with validators (indexes in validator set) voted for the proposal
+    pub fn vote_round(&mut self, voters: &[usize]) -> Result<ProposalStatus> {
+        if self.status != ProposalStatus::Pending {
+            return Ok(self.status)
+        }
+        let weight = self.votes_weight(voters)?;
+        self.rounds += 1;
+        if weight >= self.round_threshold() {
+            self.wins += 1;
+        } else {
+            self.losses += 1;
+        }
+        self.status = if self.wins >= self.setup.min_wins && self.rounds >= self.setup.min_tot_rounds {
+            ProposalStatus::Accepted(self.rounds)
+        } else if self.losses > self.setup.max_losses || self.rounds >= self.setup.max_tot_rounds {
+            ProposalStatus::Rejected(self.rounds)
+        } else {
+            ProposalStatus::Pending
+        };
+        Ok(self.status)
+    }
+
+    /// Simulate voting round with validators given by public keys
+    pub fn vote_round_by_keys(&mut self, vset: &ValidatorSet, voters: &[SigPubKey]) -> Result<ProposalStatus> {
+        let voters = voters.iter()
+            .map(|key| vset.list().iter().position(|descr| &descr.public_key == key)
+                .ok_or_else(|| error!(BlockError::NotFound(format!("validator {}", hex::encode(key.as_slice()))))))
+            .collect::<Result<Vec<_>>>()?;
+        self.vote_round(&voters)
+    }
+
+    /// Simulate voting when the same validators vote in every round until voting is finished
+    pub fn simulate(&mut self, voters: &[usize]) -> Result<ProposalStatus> {
+        while self.status == ProposalStatus::Pending {
+            self.vote_round(voters)?;
+        }
+        Ok(self.status)
+    }
+}
@@ -0,0 +1,236 @@
+/*
+* Copyright 2018-2020 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use crate::{
+    accounts::{Account, AccountStatus, StorageInfo},
+    config_params::{
+        ConfigParam18, ConfigParams, FundamentalSmcAddresses, GasLimitsPrices,
+        GlobalCapabilities, MsgForwardPrices,
+    },
+    error::BlockError,
+    messages::{CommonMsgInfo, Message, MsgAddressInt},
+    shard::MASTERCHAIN_ID,
+    transactions::{AccStatusChange, TrStoragePhase},
+    types::{AddSub, Grams},
+    Serializable,
+};
+use ton_types::{fail, Cell, Result, UInt256};
+
+///
+/// Forward fees of the message
+/// fwd_fee = mine_fee + remaining_fee
+///
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MsgFees {
+    /// full forward fee of the message
+    pub fwd_fee: Grams,
+    /// instant hypercube routing fee
+    pub ihr_fee: Grams,
+    /// part of forward fee collected by the block where message is created (first_frac)
+    pub mine_fee: Grams,
+    /// part of forward fee that travels with the message (is stored in fwd_fee of the header)
+    pub remaining_fee: Grams,
+}
+
+///
+/// Fee calculator built on storage (param 18), gas (params 20, 21)
+/// and forward (params 24, 25) prices of the blockchain config
+///
+#[derive(Clone, Debug, Default)]
+pub struct FeeCalculator {
+    storage_prices: ConfigParam18,
+    mc_gas_prices: GasLimitsPrices,
+    gas_prices: GasLimitsPrices,
+    mc_fwd_prices: MsgForwardPrices,
+    fwd_prices: MsgForwardPrices,
+    fee_in_gas_units: bool,
+    config_addr: UInt256,
+    special_accounts: FundamentalSmcAddresses,
+}
+
+impl FeeCalculator {
+    pub fn with_config(config: &ConfigParams) -> Result<Self> {
+        Ok(Self {
+            storage_prices: config.storage_prices()?,
+            mc_gas_prices: config.gas_prices(true)?,
+            gas_prices: config.gas_prices(false)?,
+            mc_fwd_prices: config.fwd_prices(true)?,
+            fwd_prices: config.fwd_prices(false)?,
+            fee_in_gas_units: config.has_capability(GlobalCapabilities::CapFeeInGasUnits),
+            config_addr: config.config_addr.clone(),
+            special_accounts: config.fundamental_smc_addr().unwrap_or_default(),
+        })
+    }
+
+    pub fn gas_prices(&self, is_masterchain: bool) -> &GasLimitsPrices {
+        match is_masterchain {
+            true => &self.mc_gas_prices,
+            false => &self.gas_prices
+        }
+    }
+
+    pub fn fwd_prices(&self, is_masterchain: bool) -> &MsgForwardPrices {
+        match is_masterchain {
+            true => &self.mc_fwd_prices,
+            false => &self.fwd_prices
+        }
+    }
+
+    /// Special accounts of masterchain don't pay storage and forward fees
+    pub fn is_special_account(&self, address: &MsgAddressInt) -> Result<bool> {
+        if address.get_workchain_id() != MASTERCHAIN_ID {
+            return Ok(false)
+        }
+        let account_id = UInt256::from_slice(&address.address().get_bytestring(0));
+        Ok(account_id == self.config_addr || self.special_accounts.check_key(&account_id)?)
+    }
+
+    /// Calculate storage fee accrued for the account from last_paid till now
+    pub fn storage_fee(&self, account: &Account, now: u32) -> Result<Grams> {
+        match (account.get_addr(), account.storage_info()) {
+            (Some(addr), Some(info)) => {
+                if self.is_special_account(addr)? {
+                    return Ok(Grams::zero())
+                }
+                self.storage_fee_for_info(info, addr.is_masterchain(), now)
+            }
+            _ => Ok(Grams::zero())
+        }
+    }
+
+    /// Calculate storage fee by storage statistics
+    pub fn storage_fee_for_info(&self, info: &StorageInfo, is_masterchain: bool, now: u32) -> Result<Grams> {
+        let fee = self.storage_prices.calc_storage_fee(info.used(), info.last_paid(), now, is_masterchain)?;
+        Grams::new(self.convert_fee(fee, is_masterchain)?)
+    }
+
+    ///
+    /// Apply storage phase to the account the same way as executor does.
+    /// Storage fee accrued since last_paid and due payment are collected from the balance.
+    /// If the balance is not enough, it is collected completely and the rest becomes due payment:
+    /// active account is frozen if due payment exceeds freeze_due_limit,
+    /// inactive one is deleted if it exceeds delete_due_limit.
+    ///
+    pub fn storage_phase(&self, account: &mut Account, now: u32) -> Result<TrStoragePhase> {
+        let addr = match account.get_addr() {
+            Some(addr) => addr.clone(),
+            None => return Ok(TrStoragePhase::default())
+        };
+        if now < account.last_paid() {
+            fail!(BlockError::InvalidArg(
+                format!("now {} is less than last_paid {} of the account", now, account.last_paid())
+            ))
+        }
+        if self.is_special_account(&addr)? {
+            return Ok(TrStoragePhase::default())
+        }
+        let mut fee = self.storage_fee(account, now)?;
+        if let Some(due_payment) = account.due_payment() {
+            if !fee.add(due_payment)? {
+                fail!(BlockError::InvalidData("storage fee overflow".to_string()))
+            }
+        }
+        account.set_due_payment(None);
+        account.set_last_paid(now);
+
+        let mut balance = account.balance_checked();
+        if balance.grams >= fee {
+            balance.grams.sub(&fee)?;
+            account.set_balance(balance);
+            return Ok(TrStoragePhase::with_params(fee, None, AccStatusChange::Unchanged))
+        }
+        let collected = std::mem::replace(&mut balance.grams, Grams::zero());
+        fee.sub(&collected)?;
+        account.set_balance(balance);
+
+        let prices = self.gas_prices(addr.is_masterchain());
+        let is_active = account.status() == AccountStatus::AccStateActive;
+        if !is_active && fee > Grams::from(prices.delete_due_limit) {
+            *account = Account::default();
+            return Ok(TrStoragePhase::with_params(collected, Some(fee), AccStatusChange::Deleted))
+        }
+        account.set_due_payment(Some(fee));
+        let status_change = if is_active && fee > Grams::from(prices.freeze_due_limit) {
+            account.try_freeze()?;
+            AccStatusChange::Frozen
+        } else {
+            AccStatusChange::Unchanged
+        };
+        Ok(TrStoragePhase::with_params(collected, Some(fee), status_change))
+    }
+
+    /// Calculate forward, IHR fees and first_frac split for the message.
+    /// For internal messages with disabled IHR, ihr_fee is zero.
+    /// For external messages the whole forward fee is collected at once.
+    pub fn msg_fees(&self, msg: &Message) -> Result<MsgFees> {
+        let is_masterchain = msg.is_masterchain();
+        if let Some(src) = msg.src_ref() {
+            if self.is_special_account(src)? {
+                return Ok(MsgFees::default())
+            }
+        }
+        let fees = self.msg_fees_for_cell(&msg.serialize()?, is_masterchain)?;
+        match msg.header() {
+            CommonMsgInfo::IntMsgInfo(header) if header.ihr_disabled => Ok(MsgFees {
+                ihr_fee: Grams::zero(),
+                ..fees
+            }),
+            CommonMsgInfo::IntMsgInfo(_) => Ok(fees),
+            _ => Ok(MsgFees {
+                fwd_fee: fees.fwd_fee,
+                ihr_fee: Grams::zero(),
+                mine_fee: fees.fwd_fee,
+                remaining_fee: Grams::zero(),
+            })
+        }
+    }
+
+    /// Calculate forward, IHR fees and first_frac split for the serialized message
+    pub fn msg_fees_for_cell(&self, msg_cell: &Cell, is_masterchain: bool) -> Result<MsgFees> {
+        let prices = self.fwd_prices(is_masterchain);
+        let fwd_fee = self.convert_fee(prices.fwd_fee(msg_cell), is_masterchain)?;
+        let ihr_fee = prices.ihr_fee(fwd_fee);
+        let mine_fee = prices.mine_fee(fwd_fee);
+        Ok(MsgFees {
+            fwd_fee: Grams::new(fwd_fee)?,
+            ihr_fee: Grams::new(ihr_fee)?,
+            mine_fee: Grams::new(mine_fee)?,
+            remaining_fee: Grams::new(fwd_fee - mine_fee)?,
+        })
+    }
+
+    /// Part of the remaining forward fee collected by transit block (next_frac)
+    pub fn transit_fee(&self, remaining_fee: &Grams, is_masterchain: bool) -> Result<Grams> {
+        Grams::new(self.fwd_prices(is_masterchain).next_fee(remaining_fee.as_u128()))
+    }
+
+    // with CapFeeInGasUnits all fees in config are in gas units and are paid as gas
+    fn convert_fee(&self, fee: u128, is_masterchain: bool) -> Result<u128> {
+        if self.fee_in_gas_units {
+            Ok(self.gas_prices(is_masterchain).calc_gas_fee(u64::try_from(fee)?))
+        } else {
+            Ok(fee)
+        }
+    }
+}
+
+///
+/// Apply storage phase to the account with the prices from the config
+///
+pub fn storage_phase(account: &mut Account, config: &ConfigParams, now: u32) -> Result<TrStoragePhase> {
+    FeeCalculator::with_config(config)?.storage_phase(account, now)
+}
+
+#[cfg(test)]
+#[path = "tests/test_fees.rs"]
+mod tests;
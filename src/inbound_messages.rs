@@ -303,6 +303,14 @@ impl InMsg {
         }
     }
 
+    /// Normalized hash of inbound external message
+    pub fn normalized_hash(&self) -> Result<Option<UInt256>> {
+        match self {
+            InMsg::External(ref x) => Ok(Some(x.read_message()?.normalized_hash()?)),
+            _ => Ok(None)
+        }
+    }
+
     ///
     /// Get message cell
     ///
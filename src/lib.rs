@@ -38,6 +38,9 @@ pub use self::accounts::*;
 pub mod messages;
 pub use self::messages::*;
 
+pub mod message_builder;
+pub use self::message_builder::*;
+
 pub mod inbound_messages;
 pub use self::inbound_messages::*;
 
@@ -50,6 +53,15 @@ pub use self::envelope_message::*;
 pub mod outbound_messages;
 pub use self::outbound_messages::*;
 
+pub mod routing;
+pub use self::routing::*;
+
+pub mod msg_descr_check;
+pub use self::msg_descr_check::*;
+
+pub mod light_client;
+pub use self::light_client::*;
+
 pub mod shard_accounts;
 pub use self::shard_accounts::*;
 
@@ -80,6 +92,24 @@ pub use self::signature::*;
 pub mod config_params;
 pub use self::config_params::*;
 
+pub mod config_json;
+pub use self::config_json::*;
+
+pub mod config_diff;
+pub use self::config_diff::*;
+
+pub mod config_proposal;
+pub use self::config_proposal::*;
+
+pub mod tlb;
+pub use self::tlb::*;
+
+pub mod fees;
+pub use self::fees::*;
+
+pub mod account_diff;
+pub use self::account_diff::*;
+
 use std::{collections::HashMap, hash::Hash};
 use ton_types::{
     error, fail, Result,
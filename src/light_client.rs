@@ -0,0 +1,215 @@
+/*
+* Copyright 2018-2020 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use ton_types::{error, fail, Result};
+
+use crate::{
+    blocks::{Block, BlockIdExt, BlockInfo},
+    config_params::{CatchainConfig, ConfigParams},
+    error::BlockError,
+    merkle_proof::{check_block_info_proof, MerkleProof},
+    shard::{MASTERCHAIN_ID, SHARD_FULL},
+    signature::BlockProof,
+    validators::ValidatorSet,
+    Deserializable,
+};
+
+// extracts block from the proof and checks that it is the block the proof is for
+fn read_proof_block(proof: &BlockProof) -> Result<(Block, BlockInfo)> {
+    let merkle_proof = MerkleProof::construct_from_cell(proof.root.clone())?;
+    let block: Block = merkle_proof.virtualize()
+        .map_err(
+            |err| BlockError::WrongMerkleProof(
+                format!("Error extracting block from proof: {}", err)
+            )
+        )?;
+    let info = check_block_info_proof(&block, &merkle_proof.hash, &proof.proof_for.root_hash)?;
+    if info.shard() != proof.proof_for.shard() || info.seq_no() != proof.proof_for.seq_no() {
+        fail!(
+            BlockError::WrongMerkleProof(
+                format!("Block info in proof doesn't correspond to block {}", proof.proof_for)
+            )
+        )
+    }
+    Ok((block, info))
+}
+
+// config of key block is stored in extra->custom->config
+fn read_key_block_config(block: &Block) -> Result<ConfigParams> {
+    block.read_extra()?
+        .read_custom()?
+        .and_then(|custom| custom.config().cloned())
+        .ok_or_else(|| error!(
+            BlockError::WrongMerkleProof("No config of key block in proof".to_string())
+        ))
+}
+
+///
+/// Light client verifying masterchain and shard blocks starting from a trusted key block.
+/// Masterchain blocks are checked by signatures of the validators of the last known key block,
+/// key blocks move it forward, shard blocks are checked through ShardHashes of masterchain block.
+///
+#[derive(Clone, Debug)]
+pub struct LightClient {
+    key_block: BlockIdExt,
+    validators: ValidatorSet,
+    cc_config: CatchainConfig,
+}
+
+impl LightClient {
+    /// Client trusting the key block (or zerostate) with given config
+    pub fn with_config(key_block: BlockIdExt, config: &ConfigParams) -> Result<Self> {
+        if !key_block.shard().is_masterchain() {
+            fail!(BlockError::InvalidArg(format!("{} is not a masterchain block", key_block)))
+        }
+        Ok(Self {
+            key_block,
+            validators: config.validator_set()?,
+            cc_config: config.catchain_config()?,
+        })
+    }
+
+    /// Client trusting the key block from the proof, its signatures are not checked
+    pub fn with_key_block_proof(proof: &BlockProof) -> Result<Self> {
+        let (block, info) = read_proof_block(proof)?;
+        if !info.key_block() {
+            fail!(BlockError::InvalidArg(format!("{} is not a key block", proof.proof_for)))
+        }
+        Self::with_config(proof.proof_for.clone(), &read_key_block_config(&block)?)
+    }
+
+    /// Last trusted key block
+    pub fn key_block(&self) -> &BlockIdExt {
+        &self.key_block
+    }
+
+    pub fn validators(&self) -> &ValidatorSet {
+        &self.validators
+    }
+
+    fn check_signatures(&self, proof: &BlockProof, info: &BlockInfo) -> Result<()> {
+        let signatures = proof.signatures.as_ref().ok_or_else(|| error!(
+            BlockError::InvalidData(format!("Proof of {} has no signatures", proof.proof_for))
+        ))?;
+        let (subset, hash_short) = self.validators.calc_subset(
+            &self.cc_config,
+            SHARD_FULL,
+            MASTERCHAIN_ID,
+            info.gen_catchain_seqno(),
+            info.gen_utime()
+        )?;
+        if hash_short != info.gen_validator_list_hash_short()
+            || hash_short != signatures.validator_info.validator_list_hash_short
+            || info.gen_catchain_seqno() != signatures.validator_info.catchain_seqno {
+            fail!(
+                BlockError::InvalidData(format!(
+                    "Validator set of {} doesn't correspond to key block {}", proof.proof_for, self.key_block
+                ))
+            )
+        }
+        let data = Block::build_data_for_sign(&proof.proof_for.root_hash, &proof.proof_for.file_hash);
+        let weight = signatures.pure_signatures.check_signatures(&subset, &data)?;
+        let total_weight = subset.iter().map(|vd| vd.weight as u128).sum::<u128>();
+        if weight as u128 * 3 <= total_weight * 2 {
+            fail!(
+                BlockError::InvalidData(format!(
+                    "Signatures weight {} of {} is not more than 2/3 of {}", weight, proof.proof_for, total_weight
+                ))
+            )
+        }
+        Ok(())
+    }
+
+    fn check_mc_proof(&self, proof: &BlockProof) -> Result<(Block, BlockInfo)> {
+        if !proof.proof_for.shard().is_masterchain() {
+            fail!(BlockError::InvalidArg(format!("{} is not a masterchain block", proof.proof_for)))
+        }
+        let (block, info) = read_proof_block(proof)?;
+        if info.prev_key_block_seqno() != self.key_block.seq_no() {
+            fail!(
+                BlockError::InvalidData(format!(
+                    "Previous key block of {} is {}, not {}",
+                    proof.proof_for, info.prev_key_block_seqno(), self.key_block.seq_no()
+                ))
+            )
+        }
+        self.check_signatures(proof, &info)?;
+        Ok((block, info))
+    }
+
+    /// Verify masterchain block signed by the validators of the last trusted key block
+    pub fn check_mc_block(&self, proof: &BlockProof) -> Result<BlockInfo> {
+        Ok(self.check_mc_proof(proof)?.1)
+    }
+
+    /// Verify next key block and trust it with its current validator set (param 34)
+    pub fn apply_key_block(&mut self, proof: &BlockProof) -> Result<BlockInfo> {
+        let (block, info) = self.check_mc_proof(proof)?;
+        if !info.key_block() {
+            fail!(BlockError::InvalidArg(format!("{} is not a key block", proof.proof_for)))
+        }
+        *self = Self::with_config(proof.proof_for.clone(), &read_key_block_config(&block)?)?;
+        Ok(info)
+    }
+
+    /// Verify key blocks one by one, the last of them becomes trusted
+    pub fn apply_key_blocks<'a>(&mut self, proofs: impl IntoIterator<Item = &'a BlockProof>) -> Result<()> {
+        for proof in proofs {
+            self.apply_key_block(proof)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Verify shard block by the verified masterchain block.
+    /// Proof of masterchain block must contain its ShardHashes (extra->custom->shards),
+    /// shard block must be registered there as top block of its shard.
+    ///
+    pub fn check_shard_block(&self, mc_proof: &BlockProof, shard_proof: &MerkleProof) -> Result<BlockInfo> {
+        let (mc_block, _) = self.check_mc_proof(mc_proof)?;
+        let custom = mc_block.read_extra()
+            .and_then(|extra| extra.read_custom())
+            .map_err(
+                |err| BlockError::WrongMerkleProof(
+                    format!("Error extracting shard hashes from proof: {}", err)
+                )
+            )?
+            .ok_or_else(|| error!(
+                BlockError::WrongMerkleProof("No shard hashes in masterchain block proof".to_string())
+            ))?;
+
+        let block: Block = shard_proof.virtualize()
+            .map_err(
+                |err| BlockError::WrongMerkleProof(
+                    format!("Error extracting block from proof: {}", err)
+                )
+            )?;
+        let info = block.read_info()?;
+        let record = custom.shards().get_shard(info.shard())?.ok_or_else(|| error!(
+            BlockError::NotFound(format!("shard {} in masterchain block {}", info.shard(), mc_proof.proof_for))
+        ))?;
+        check_block_info_proof(&block, &shard_proof.hash, &record.block_id.root_hash)?;
+        if record.block_id.seq_no() != info.seq_no() {
+            fail!(
+                BlockError::WrongMerkleProof(
+                    format!("Block info in proof doesn't correspond to block {}", record.block_id)
+                )
+            )
+        }
+        Ok(info)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/test_light_client.rs"]
+mod tests;
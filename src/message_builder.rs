@@ -0,0 +1,286 @@
+/*
+* Copyright 2018-2020 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use ton_types::{fail, BuilderData, Cell, Result, SliceData, UInt256};
+
+use crate::{
+    error::BlockError,
+    messages::{
+        CommonMsgInfo, ExtOutMessageHeader, ExternalInboundMessageHeader,
+        InternalMessageHeader, Message, MsgAddress, MsgAddressExt, MsgAddressInt,
+        MsgAddressIntOrNone, StateInit,
+    },
+    types::{CurrencyCollection, Grams, UnixTime32},
+    Deserializable,
+};
+
+///
+/// Logical time and unix time used for created_lt and created_at of built messages.
+/// Every message built with the context gets its own logical time.
+///
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MessageContext {
+    pub created_lt: u64,
+    pub created_at: u32,
+}
+
+impl MessageContext {
+    pub const fn with_values(created_lt: u64, created_at: u32) -> Self {
+        Self { created_lt, created_at }
+    }
+
+    /// Return current values and move logical time forward
+    pub fn next(&mut self) -> (u64, u32) {
+        let result = (self.created_lt, self.created_at);
+        self.created_lt += 1;
+        result
+    }
+}
+
+///
+/// Header of one of message kinds used by MessageBuilder
+///
+pub trait MessageHeaderKind {
+    fn set_created(&mut self, created_lt: u64, created_at: u32);
+    fn into_common(self) -> CommonMsgInfo;
+}
+
+impl MessageHeaderKind for InternalMessageHeader {
+    fn set_created(&mut self, created_lt: u64, created_at: u32) {
+        self.created_lt = created_lt;
+        self.created_at = UnixTime32::new(created_at);
+    }
+    fn into_common(self) -> CommonMsgInfo {
+        CommonMsgInfo::IntMsgInfo(self)
+    }
+}
+
+impl MessageHeaderKind for ExternalInboundMessageHeader {
+    // inbound external message has no creation time
+    fn set_created(&mut self, _created_lt: u64, _created_at: u32) {}
+    fn into_common(self) -> CommonMsgInfo {
+        CommonMsgInfo::ExtInMsgInfo(self)
+    }
+}
+
+impl MessageHeaderKind for ExtOutMessageHeader {
+    fn set_created(&mut self, created_lt: u64, created_at: u32) {
+        self.created_lt = created_lt;
+        self.created_at = UnixTime32::new(created_at);
+    }
+    fn into_common(self) -> CommonMsgInfo {
+        CommonMsgInfo::ExtOutMsgInfo(self)
+    }
+}
+
+///
+/// Serialized message with its hash
+///
+#[derive(Clone, Debug)]
+pub struct BuiltMessage {
+    pub message: Message,
+    pub cell: Cell,
+    pub hash: UInt256,
+}
+
+///
+/// Builder of internal, inbound external and outbound external messages.
+/// Addresses are checked to be of the kind allowed for the message.
+/// If layout is not set, state init and body are put inline when they fit
+/// into the root cell and moved to references otherwise.
+///
+#[derive(Clone, Debug)]
+pub struct MessageBuilder<H: MessageHeaderKind> {
+    header: H,
+    init: Option<StateInit>,
+    body: Option<SliceData>,
+    body_to_ref: Option<bool>,
+    init_to_ref: Option<bool>,
+}
+
+fn check_int_address(address: &MsgAddressInt) -> Result<()> {
+    match address {
+        MsgAddressInt::AddrStd(addr) => {
+            if addr.address.remaining_bits() != 256 {
+                fail!(BlockError::InvalidArg(
+                    format!("standard address must have 256 bits, not {}", addr.address.remaining_bits())
+                ))
+            }
+        }
+        MsgAddressInt::AddrVar(addr) => {
+            if addr.address.remaining_bits() != addr.addr_len.as_usize() {
+                fail!(BlockError::InvalidArg(format!(
+                    "address length {} doesn't match addr_len {}",
+                    addr.address.remaining_bits(), addr.addr_len
+                )))
+            }
+        }
+    }
+    address.check_anycast()
+}
+
+fn int_address(address: MsgAddress, name: &str) -> Result<MsgAddressInt> {
+    match address.to_msg_addr_int() {
+        Some(address) => {
+            check_int_address(&address)?;
+            Ok(address)
+        }
+        None => fail!(BlockError::InvalidArg(format!("{} must be an internal address", name)))
+    }
+}
+
+fn int_address_or_none(address: MsgAddress, name: &str) -> Result<MsgAddressIntOrNone> {
+    match address {
+        MsgAddress::AddrNone => Ok(MsgAddressIntOrNone::None),
+        address => Ok(MsgAddressIntOrNone::Some(int_address(address, name)?))
+    }
+}
+
+fn ext_address(address: MsgAddress, name: &str) -> Result<MsgAddressExt> {
+    match address {
+        MsgAddress::AddrNone => Ok(MsgAddressExt::AddrNone),
+        MsgAddress::AddrExt(addr) => {
+            if addr.external_address.remaining_bits() != addr.len.as_usize() {
+                fail!(BlockError::InvalidArg(format!("{} has wrong length", name)))
+            }
+            Ok(MsgAddressExt::AddrExtern(addr))
+        }
+        _ => fail!(BlockError::InvalidArg(format!("{} must be an external address", name)))
+    }
+}
+
+impl<H: MessageHeaderKind> MessageBuilder<H> {
+    fn with_header(header: H) -> Self {
+        Self {
+            header,
+            init: None,
+            body: None,
+            body_to_ref: None,
+            init_to_ref: None,
+        }
+    }
+
+    /// Set created_lt and created_at from the context and move its logical time forward
+    pub fn with_context(mut self, context: &mut MessageContext) -> Self {
+        let (created_lt, created_at) = context.next();
+        self.header.set_created(created_lt, created_at);
+        self
+    }
+
+    pub fn with_created(mut self, created_lt: u64, created_at: u32) -> Self {
+        self.header.set_created(created_lt, created_at);
+        self
+    }
+
+    pub fn with_state_init(mut self, init: StateInit) -> Self {
+        self.init = Some(init);
+        self
+    }
+
+    pub fn with_body(mut self, body: SliceData) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    pub fn with_body_cell(self, body: Cell) -> Result<Self> {
+        Ok(self.with_body(SliceData::load_cell(body)?))
+    }
+
+    /// Force placement of body and state init (true means in reference)
+    pub fn with_layout(mut self, body_to_ref: bool, init_to_ref: bool) -> Self {
+        self.body_to_ref = Some(body_to_ref);
+        self.init_to_ref = Some(init_to_ref);
+        self
+    }
+
+    /// Serialize message and calculate its hash
+    pub fn build(self) -> Result<BuiltMessage> {
+        let mut message = Message::default();
+        message.set_header(self.header.into_common());
+        if let Some(init) = self.init {
+            message.set_state_init(init);
+        }
+        if let Some(body) = self.body {
+            message.set_body(body);
+        }
+        let mut builder = BuilderData::new();
+        message.serialize_with_params(&mut builder, &self.body_to_ref, &self.init_to_ref)?;
+        let cell = builder.into_cell()?;
+        Ok(BuiltMessage {
+            // read back to keep chosen layout in the message
+            message: Message::construct_from_cell(cell.clone())?,
+            hash: cell.repr_hash(),
+            cell,
+        })
+    }
+}
+
+impl MessageBuilder<InternalMessageHeader> {
+    /// Internal message, source may be AddrNone to be filled by transaction
+    pub fn internal(src: MsgAddress, dst: MsgAddress, value: CurrencyCollection) -> Result<Self> {
+        let mut header = InternalMessageHeader::new();
+        header.src = int_address_or_none(src, "source")?;
+        header.dst = int_address(dst, "destination")?;
+        header.value = value;
+        header.ihr_disabled = true;
+        Ok(Self::with_header(header))
+    }
+
+    pub fn with_bounce(mut self, bounce: bool) -> Self {
+        self.header.bounce = bounce;
+        self
+    }
+
+    pub fn with_bounced(mut self, bounced: bool) -> Self {
+        self.header.bounced = bounced;
+        self
+    }
+
+    pub fn with_ihr_disabled(mut self, ihr_disabled: bool) -> Self {
+        self.header.ihr_disabled = ihr_disabled;
+        self
+    }
+
+    pub fn with_fees(mut self, ihr_fee: Grams, fwd_fee: Grams) -> Self {
+        self.header.ihr_fee = ihr_fee;
+        self.header.fwd_fee = fwd_fee;
+        self
+    }
+}
+
+impl MessageBuilder<ExternalInboundMessageHeader> {
+    pub fn external_inbound(src: MsgAddress, dst: MsgAddress) -> Result<Self> {
+        let header = ExternalInboundMessageHeader::new(
+            ext_address(src, "source")?,
+            int_address(dst, "destination")?,
+        );
+        Ok(Self::with_header(header))
+    }
+
+    pub fn with_import_fee(mut self, import_fee: Grams) -> Self {
+        self.header.import_fee = import_fee;
+        self
+    }
+}
+
+impl MessageBuilder<ExtOutMessageHeader> {
+    pub fn external_outbound(src: MsgAddress, dst: MsgAddress) -> Result<Self> {
+        let header = ExtOutMessageHeader {
+            src: int_address_or_none(src, "source")?,
+            dst: ext_address(dst, "destination")?,
+            created_lt: 0,
+            created_at: UnixTime32::default(),
+        };
+        Ok(Self::with_header(header))
+    }
+}
@@ -14,11 +14,14 @@
 use crate::GetRepresentationHash;
 use crate::{
     blocks::Block,
+    config_params::{CapabilitiesSet, GlobalCapabilities, MsgForwardPrices, WorkchainDescr},
     define_HashmapE,
     error::BlockError,
+    fees::FeeCalculator,
     hashmapaug::HashmapAugType,
     merkle_proof::MerkleProof,
     shard::MASTERCHAIN_ID,
+    signature::{sign_body, verify_body, SigPubKey},
     types::{AddSub, CurrencyCollection, Grams, Number5, Number9, UnixTime32},
     Deserializable, MaybeDeserialize, MaybeSerialize, Serializable,
 };
@@ -51,17 +54,76 @@ _ MsgAddressExt = MsgAddress;
  */
 
 impl AnycastInfo {
+    /// Maximal depth of anycast: depth:(#<= 30) { depth >= 1 }
+    pub const MAX_DEPTH: usize = 30;
+
     pub fn with_rewrite_pfx(pfx: SliceData) -> Result<Self> {
+        Self::check_depth(pfx.remaining_bits())?;
         Ok(Self {
             depth: Number5::new(pfx.remaining_bits() as u32)?,
             rewrite_pfx: pfx
         })
     }
     pub fn set_rewrite_pfx(&mut self, pfx: SliceData) -> Result<()>{
+        Self::check_depth(pfx.remaining_bits())?;
         self.depth = Number5::new(pfx.remaining_bits() as u32)?;
         self.rewrite_pfx = pfx;
         Ok(())
     }
+
+    fn check_depth(depth: usize) -> Result<()> {
+        if depth == 0 || depth > Self::MAX_DEPTH {
+            fail!(BlockError::InvalidArg(
+                format!("anycast depth must be from 1 to {}, not {}", Self::MAX_DEPTH, depth)
+            ))
+        }
+        Ok(())
+    }
+
+    ///
+    /// Check anycast info to be applicable to the address of addr_len bits:
+    /// depth must be valid, match the length of rewrite prefix and not exceed
+    /// the length of the address
+    ///
+    pub fn check(&self, addr_len: usize) -> Result<()> {
+        let depth = self.depth.as_usize();
+        Self::check_depth(depth)?;
+        if self.rewrite_pfx.remaining_bits() != depth {
+            fail!(BlockError::InvalidArg(format!(
+                "anycast rewrite prefix has {} bits instead of {}",
+                self.rewrite_pfx.remaining_bits(), depth
+            )))
+        }
+        if depth > addr_len {
+            fail!(BlockError::InvalidArg(
+                format!("anycast depth {} is greater than address length {}", depth, addr_len)
+            ))
+        }
+        Ok(())
+    }
+
+    ///
+    /// Check anycast depth against the description of the workchain of the address.
+    /// Anycast can't select shards deeper than the workchain can be split.
+    ///
+    pub fn check_for_workchain(&self, descr: &WorkchainDescr) -> Result<()> {
+        if self.depth.as_u32() > descr.max_split() as u32 {
+            fail!(BlockError::InvalidArg(format!(
+                "anycast depth {} is greater than max_split {} of the workchain",
+                self.depth, descr.max_split()
+            )))
+        }
+        Ok(())
+    }
+
+    ///
+    /// Replace first depth bits of account id with rewrite prefix
+    ///
+    pub fn rewrite(&self, account_id: &AccountId) -> Result<AccountId> {
+        let mut account_id = account_id.clone();
+        account_id.overwrite_prefix(&self.rewrite_pfx)?;
+        Ok(account_id)
+    }
 }
 
 impl Serializable for AnycastInfo {
@@ -93,6 +155,9 @@ _ MsgAddressExt = MsgAddress;
 impl MsgAddrVar {
     pub fn with_address(anycast: Option<AnycastInfo>, workchain_id: i32, address: SliceData) -> Result<MsgAddrVar> {
         let addr_len = Number9::new(address.remaining_bits() as u32)?;
+        if let Some(anycast) = &anycast {
+            anycast.check(addr_len.as_usize())?;
+        }
         Ok(MsgAddrVar { anycast, addr_len, workchain_id, address })
     }
 }
@@ -153,6 +218,122 @@ impl fmt::Display for MsgAddrStd {
     }
 }
 
+/*
+User-friendly address representation (36 bytes encoded with base64 or base64url):
+    flags:uint8 workchain_id:int8 address:uint256 crc16:uint16
+flags: 0x11 - bounceable, 0x51 - non-bounceable, 0x80 is added for testnet-only addresses
+crc16 is CRC16-XMODEM of the first 34 bytes
+*/
+
+const FRIENDLY_ADDR_LEN: usize = 36;
+const FRIENDLY_ADDR_STR_LEN: usize = 48;
+const FRIENDLY_ADDR_BOUNCEABLE_TAG: u8 = 0x11;
+const FRIENDLY_ADDR_NON_BOUNCEABLE_TAG: u8 = 0x51;
+const FRIENDLY_ADDR_TESTNET_FLAG: u8 = 0x80;
+const FRIENDLY_ADDR_CRC: crc::Crc<u16> = crc::Crc::<u16>::new(&crc::CRC_16_XMODEM);
+
+///
+/// Flags of user-friendly address representation
+///
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct FriendlyAddressFlags {
+    pub bounceable: bool,
+    pub testnet: bool,
+    pub url_safe: bool,
+}
+
+impl FriendlyAddressFlags {
+    pub const fn with_params(bounceable: bool, testnet: bool, url_safe: bool) -> Self {
+        Self { bounceable, testnet, url_safe }
+    }
+
+    fn tag(&self) -> u8 {
+        let tag = if self.bounceable {
+            FRIENDLY_ADDR_BOUNCEABLE_TAG
+        } else {
+            FRIENDLY_ADDR_NON_BOUNCEABLE_TAG
+        };
+        if self.testnet {
+            tag | FRIENDLY_ADDR_TESTNET_FLAG
+        } else {
+            tag
+        }
+    }
+
+    fn from_tag(tag: u8, url_safe: bool) -> Result<Self> {
+        let testnet = tag & FRIENDLY_ADDR_TESTNET_FLAG != 0;
+        let bounceable = match tag & !FRIENDLY_ADDR_TESTNET_FLAG {
+            FRIENDLY_ADDR_BOUNCEABLE_TAG => true,
+            FRIENDLY_ADDR_NON_BOUNCEABLE_TAG => false,
+            _ => fail!(
+                BlockError::InvalidArg(
+                    format!("wrong flags 0x{:02x} of user-friendly address", tag)
+                )
+            )
+        };
+        Ok(Self { bounceable, testnet, url_safe })
+    }
+}
+
+impl MsgAddrStd {
+    ///
+    /// Parse user-friendly (base64 or base64url) representation of address
+    ///
+    pub fn from_user_friendly(string: &str) -> Result<(Self, FriendlyAddressFlags)> {
+        if string.len() != FRIENDLY_ADDR_STR_LEN {
+            fail!(
+                BlockError::InvalidArg(
+                    format!(
+                        "user-friendly address should be {} characters long, but it is {}",
+                        FRIENDLY_ADDR_STR_LEN, string.len()
+                    )
+                )
+            )
+        }
+        let url_safe = string.contains(|c| c == '-' || c == '_');
+        let config = if url_safe { base64::URL_SAFE } else { base64::STANDARD };
+        let bytes = base64::decode_config(string, config).map_err(
+            |err| BlockError::InvalidArg(format!("user-friendly address is not correct base64: {}", err))
+        )?;
+        if bytes.len() != FRIENDLY_ADDR_LEN {
+            fail!(
+                BlockError::InvalidArg(
+                    format!("user-friendly address should contain {} bytes", FRIENDLY_ADDR_LEN)
+                )
+            )
+        }
+        let crc = u16::from_be_bytes([bytes[34], bytes[35]]);
+        if crc != FRIENDLY_ADDR_CRC.checksum(&bytes[..34]) {
+            fail!(BlockError::InvalidArg("wrong checksum of user-friendly address".to_string()))
+        }
+        let flags = FriendlyAddressFlags::from_tag(bytes[0], url_safe)?;
+        let mut address = [0; 32];
+        address.copy_from_slice(&bytes[2..34]);
+        Ok((Self::with_address(None, bytes[1] as i8, AccountId::from(address)), flags))
+    }
+
+    ///
+    /// Get user-friendly (base64 or base64url) representation of address
+    ///
+    pub fn to_user_friendly(&self, flags: FriendlyAddressFlags) -> Result<String> {
+        if self.anycast.is_some() {
+            fail!(
+                BlockError::InvalidArg(
+                    "anycast address has no user-friendly representation".to_string()
+                )
+            )
+        }
+        let mut bytes = Vec::with_capacity(FRIENDLY_ADDR_LEN);
+        bytes.push(flags.tag());
+        bytes.push(self.workchain_id as u8);
+        bytes.extend_from_slice(&self.address.get_bytestring(0));
+        let crc = FRIENDLY_ADDR_CRC.checksum(&bytes);
+        bytes.extend_from_slice(&crc.to_be_bytes());
+        let config = if flags.url_safe { base64::URL_SAFE } else { base64::STANDARD };
+        Ok(base64::encode_config(&bytes, config))
+    }
+}
+
 impl MsgAddrExt {
     pub fn with_address(address: SliceData) -> Result<Self>{
         if address.remaining_bits() > Number9::get_max_len(){
@@ -237,6 +418,9 @@ impl MsgAddress {
     }
 
     pub fn with_standart(anycast: Option<AnycastInfo>, workchain_id: i8, address: AccountId) -> Result<Self> {
+        if let Some(anycast) = &anycast {
+            anycast.check(address.remaining_bits())?;
+        }
         Ok(MsgAddress::AddrStd(MsgAddrStd::with_address(anycast, workchain_id, address)))
     }
 
@@ -271,6 +455,10 @@ impl FromStr for MsgAddress {
         if len == 0 {
             fail!(BlockError::InvalidArg("bad split".to_string()))
         }
+        if len == 1 && string.len() == FRIENDLY_ADDR_STR_LEN {
+            let (addr, _flags) = MsgAddrStd::from_user_friendly(string)?;
+            return Ok(MsgAddress::AddrStd(addr))
+        }
         if parts[len - 1].is_empty() {
             if len == 1 {
                 return Ok(MsgAddress::AddrNone)
@@ -379,6 +567,9 @@ impl MsgAddressInt {
         Ok(MsgAddressInt::AddrVar(MsgAddrVar::with_address(anycast, workchain_id, address)?))
     }
     pub fn with_standart(anycast: Option<AnycastInfo>, workchain_id: i8, address: AccountId) -> Result<Self> {
+        if let Some(anycast) = &anycast {
+            anycast.check(address.remaining_bits())?;
+        }
         Ok(MsgAddressInt::AddrStd(MsgAddrStd::with_address(anycast, workchain_id, address)))
     }
     pub fn get_address(&self) -> SliceData { self.address() }
@@ -402,6 +593,49 @@ impl MsgAddressInt {
             MsgAddressInt::AddrVar(addr_var) => addr_var.anycast.clone()
         }
     }
+    pub fn is_anycast(&self) -> bool {
+        match self {
+            MsgAddressInt::AddrStd(addr_std) => addr_std.anycast.is_some(),
+            MsgAddressInt::AddrVar(addr_var) => addr_var.anycast.is_some()
+        }
+    }
+
+    ///
+    /// Account id with prefix rewritten by anycast info, if any.
+    /// It is used for routing and as the address of the destination account.
+    ///
+    pub fn rewritten_address(&self) -> Result<AccountId> {
+        match self {
+            MsgAddressInt::AddrStd(MsgAddrStd { anycast: Some(anycast), address, .. }) |
+            MsgAddressInt::AddrVar(MsgAddrVar { anycast: Some(anycast), address, .. }) => anycast.rewrite(address),
+            _ => Ok(self.address())
+        }
+    }
+
+    ///
+    /// Check anycast info of the address to be consistent with the address length
+    ///
+    pub fn check_anycast(&self) -> Result<()> {
+        match self {
+            MsgAddressInt::AddrStd(MsgAddrStd { anycast: Some(anycast), address, .. }) |
+            MsgAddressInt::AddrVar(MsgAddrVar { anycast: Some(anycast), address, .. }) => {
+                anycast.check(address.remaining_bits())
+            }
+            _ => Ok(())
+        }
+    }
+
+    ///
+    /// Check anycast info of the address against the description of its workchain
+    ///
+    pub fn check_anycast_for_workchain(&self, descr: &WorkchainDescr) -> Result<()> {
+        self.check_anycast()?;
+        match self.rewrite_pfx() {
+            Some(anycast) => anycast.check_for_workchain(descr),
+            None => Ok(())
+        }
+    }
+
     pub fn extract_std_address(&self, do_rewrite: bool) -> Result<(i32, AccountId)> {
         let (workchain_id, mut account_id, anycast_opt) = match self {
             MsgAddressInt::AddrStd(addr_std) => (addr_std.workchain_id as i32, addr_std.address.clone(), &addr_std.anycast),
@@ -410,7 +644,7 @@ impl MsgAddressInt {
 
         if let Some(ref anycast) = anycast_opt {
             if do_rewrite {
-                account_id.overwrite_prefix(&anycast.rewrite_pfx)?;
+                account_id = anycast.rewrite(&account_id)?;
             }
         }
 
@@ -420,6 +654,29 @@ impl MsgAddressInt {
     pub fn is_masterchain(&self) -> bool {
         self.get_workchain_id() == MASTERCHAIN_ID
     }
+
+    ///
+    /// Parse user-friendly (base64 or base64url) representation of address
+    ///
+    pub fn from_user_friendly(string: &str) -> Result<(Self, FriendlyAddressFlags)> {
+        let (addr, flags) = MsgAddrStd::from_user_friendly(string)?;
+        Ok((MsgAddressInt::AddrStd(addr), flags))
+    }
+
+    ///
+    /// Get user-friendly (base64 or base64url) representation of address.
+    /// Only standard addresses without anycast can be represented
+    ///
+    pub fn to_user_friendly(&self, flags: FriendlyAddressFlags) -> Result<String> {
+        match self {
+            MsgAddressInt::AddrStd(addr) => addr.to_user_friendly(flags),
+            MsgAddressInt::AddrVar(_) => fail!(
+                BlockError::InvalidArg(
+                    "variable length address has no user-friendly representation".to_string()
+                )
+            )
+        }
+    }
 }
 
 impl Serializable for MsgAddressInt {
@@ -459,7 +716,6 @@ for these formats.
 Known limitations:
 1. For account addreses:
     * we don't serialize the workchain id;
-    * anycast is not supported (is supposed to be `nothing`);
     * only standard 256-bit addresses are supported.
 
 2. Instead of CurrencyCollection, Grams type is used.
@@ -1278,6 +1534,427 @@ impl Message {
         }
     }
 
+    ///
+    /// Check that the message deploys account by its StateInit: destination account id
+    /// (before anycast rewrite) must be the hash of StateInit, anycast depth must be equal
+    /// to split_depth and StateInit must have code to create active account.
+    /// Returns init_code_hash to be stored in the account if CapInitCodeHash is enabled
+    /// (the same as Account::from_message_by_init_code_hash does).
+    ///
+    pub fn check_deploy(&self, caps: &CapabilitiesSet) -> Result<Option<UInt256>> {
+        let init = self.state_init().ok_or_else(|| error!(
+            BlockError::InvalidData("message has no StateInit".to_string())
+        ))?;
+        let dst = self.dst_ref().ok_or_else(|| error!(
+            BlockError::InvalidData("message has no internal destination".to_string())
+        ))?;
+        let hash = init.hash()?;
+        let (_, account_id) = dst.extract_std_address(false)?;
+        if account_id.remaining_bits() != 256 || UInt256::from_slice(&account_id.get_bytestring(0)) != hash {
+            fail!(BlockError::InvalidData(
+                format!("destination {} doesn't match StateInit hash {:x}", dst, hash)
+            ))
+        }
+        let split_depth = init.split_depth().map(|depth| depth.as_u32()).unwrap_or_default();
+        let anycast_depth = dst.rewrite_pfx().map(|anycast| anycast.depth.as_u32()).unwrap_or_default();
+        if split_depth != anycast_depth {
+            fail!(BlockError::InvalidData(format!(
+                "anycast depth {} of destination doesn't match split_depth {} of StateInit",
+                anycast_depth, split_depth
+            )))
+        }
+        let code = init.code().ok_or_else(|| error!(
+            BlockError::InvalidData("StateInit has no code".to_string())
+        ))?;
+        match caps.contains(GlobalCapabilities::CapInitCodeHash) {
+            true => Ok(Some(code.repr_hash())),
+            false => Ok(None)
+        }
+    }
+
+    ///
+    /// Hash of inbound external message which doesn't depend on the way it was relayed:
+    /// src is AddrNone, import_fee is zero, StateInit is dropped and body is stored in reference.
+    /// The same message has the same normalized hash in InMsg and Transaction.
+    ///
+    pub fn normalized_hash(&self) -> Result<UInt256> {
+        let header = match self.ext_in_header() {
+            Some(header) => ExternalInboundMessageHeader::new(MsgAddressExt::AddrNone, header.dst.clone()),
+            None => fail!(BlockError::InvalidOperation(
+                "normalized hash is defined only for inbound external messages".to_string()
+            ))
+        };
+        let mut message = Message::with_ext_in_header(header);
+        message.set_body(self.body.clone().unwrap_or_default());
+        let mut builder = BuilderData::new();
+        message.serialize_with_params(&mut builder, &Some(true), &Some(false))?;
+        Ok(builder.into_cell()?.repr_hash())
+    }
+
+    ///
+    /// Sign the body of inbound external message with the keypair:
+    /// 512-bit signature of the body hash is prepended to the body.
+    /// Signature id should be taken from signature_id() for the network.
+    ///
+    pub fn sign_body(&mut self, keypair: &ed25519_dalek::Keypair, signature_id: Option<i32>) -> Result<()> {
+        if !self.is_inbound_external() {
+            fail!(BlockError::InvalidOperation(
+                "only inbound external message body can be signed".to_string()
+            ))
+        }
+        let body = sign_body(&self.body.clone().unwrap_or_default(), keypair, signature_id)?;
+        self.set_body(body);
+        Ok(())
+    }
+
+    ///
+    /// Check the signature in the body of inbound external message
+    ///
+    pub fn verify_body(&self, public_key: &SigPubKey, signature_id: Option<i32>) -> Result<bool> {
+        if !self.is_inbound_external() {
+            fail!(BlockError::InvalidOperation(
+                "only inbound external message body can be verified".to_string()
+            ))
+        }
+        match &self.body {
+            Some(body) => verify_body(body, public_key, signature_id),
+            None => fail!(BlockError::InvalidData("message has no body".to_string()))
+        }
+    }
+
+    ///
+    /// Make bounce reply for the internal message the same way as validator does in bounce phase.
+    /// With CapBounceMsgBody body is 0xffffffff followed by first 256 bits of original body,
+    /// with CapFullBodyInBounced the whole original body is added as reference.
+    /// Forward fee is calculated by `fwd_prices` and deducted from the value of the message.
+    /// Returns None if message must not be bounced or its value doesn't cover forward fee.
+    ///
+    pub fn make_bounced(
+        &self,
+        caps: &CapabilitiesSet,
+        fwd_prices: &MsgForwardPrices,
+        now: u32,
+        lt: u64
+    ) -> Result<Option<Message>> {
+        let value = match self.int_header() {
+            Some(header) => header.value.clone(),
+            None => return Ok(None)
+        };
+        let mut bounced = match self.bounced_reply(caps, value, now, lt)? {
+            Some(bounced) => bounced,
+            None => return Ok(None)
+        };
+        // root cell is not counted so fee doesn't depend on header fields
+        let fwd_fee = fwd_prices.fwd_fee(&bounced.serialize()?);
+        let mine_fee = fwd_prices.mine_fee(fwd_fee);
+        if !bounced.deduct_bounce_fee(&Grams::new(fwd_fee)?, Grams::new(fwd_fee - mine_fee)?)? {
+            return Ok(None)
+        }
+        Ok(Some(bounced))
+    }
+
+    ///
+    /// Same as `make_bounced` but forward fee is calculated by the fee calculator
+    /// (taking CapFeeInGasUnits into account) and deducted from `value`,
+    /// the value remaining after compute and action phases (not the original one).
+    ///
+    pub fn make_bounced_with_fees(
+        &self,
+        caps: &CapabilitiesSet,
+        fees: &FeeCalculator,
+        value: CurrencyCollection,
+        now: u32,
+        lt: u64
+    ) -> Result<Option<Message>> {
+        let mut bounced = match self.bounced_reply(caps, value, now, lt)? {
+            Some(bounced) => bounced,
+            None => return Ok(None)
+        };
+        // bounce is paid by the account, so prices of its workchain are used
+        let is_masterchain = matches!(bounced.src_ref(), Some(src) if src.is_masterchain());
+        let msg_fees = fees.msg_fees_for_cell(&bounced.serialize()?, is_masterchain)?;
+        if !bounced.deduct_bounce_fee(&msg_fees.fwd_fee, msg_fees.remaining_fee)? {
+            return Ok(None)
+        }
+        Ok(Some(bounced))
+    }
+
+    // bounce reply without fees deducted
+    fn bounced_reply(
+        &self,
+        caps: &CapabilitiesSet,
+        value: CurrencyCollection,
+        now: u32,
+        lt: u64
+    ) -> Result<Option<Message>> {
+        let header = match self.int_header() {
+            Some(header) if header.bounce && !header.bounced => header,
+            _ => return Ok(None)
+        };
+        let src = match &header.src {
+            MsgAddressIntOrNone::Some(src) => src.clone(),
+            MsgAddressIntOrNone::None => return Ok(None)
+        };
+        let mut bounced_header = InternalMessageHeader::with_addresses(
+            header.dst.clone(),
+            src,
+            value,
+        );
+        bounced_header.bounced = true;
+        bounced_header.created_lt = lt;
+        bounced_header.created_at = UnixTime32::new(now);
+        let mut bounced = Message::with_int_header(bounced_header);
+        if caps.contains(GlobalCapabilities::CapBounceMsgBody) {
+            let mut builder = BuilderData::new();
+            builder.append_u32(0xFFFF_FFFF)?;
+            if let Some(body) = self.body() {
+                let bits = std::cmp::min(256, body.remaining_bits());
+                builder.checked_append_references_and_data(&body.clone().get_next_slice(bits)?)?;
+                if caps.contains(GlobalCapabilities::CapFullBodyInBounced) {
+                    builder.checked_append_reference(body.into_cell())?;
+                }
+            }
+            bounced.set_body(SliceData::load_builder(builder)?);
+        }
+        Ok(Some(bounced))
+    }
+
+    // returns false if value doesn't cover forward fee
+    fn deduct_bounce_fee(&mut self, fwd_fee: &Grams, remaining_fee: Grams) -> Result<bool> {
+        if let Some(header) = self.int_header_mut() {
+            if header.value.grams < *fwd_fee {
+                return Ok(false)
+            }
+            header.value.grams.sub(fwd_fee)?;
+            header.fwd_fee = remaining_fee;
+        }
+        Ok(true)
+    }
+
+    ///
+    /// Get value transmitted by the message
+    /// Set Logical Time and UNIX time for
+    /// Internal and External outbound messages
+    ///
+    pub fn set_at_and_lt(&mut self, at: u32, lt: u64) {
+        match self.header {
+            CommonMsgInfo::IntMsgInfo(ref mut header) => {
+                header.created_at = UnixTime32::new(at);
+                header.created_lt = lt;
+            },
+            CommonMsgInfo::ExtOutMsgInfo(ref mut header) => {
+                header.created_at = UnixTime32::new(at);
+                header.created_lt = lt;
+            },
+            _ => ()
+        };
+    }
+    pub fn set_src(&mut self, address: MsgAddressIntOrNone) {
+        match self.header {
+            CommonMsgInfo::IntMsgInfo(ref mut header) => {
+                header.src = address;
+            }
+            CommonMsgInfo::ExtOutMsgInfo(ref mut header) => {
+                header.src = address;
+            }
+            _ => ()
+        };
+    }
+    pub fn set_src_address(&mut self, src: MsgAddressInt) {
+        match &mut self.header {
+            CommonMsgInfo::IntMsgInfo(header) => {
+                header.src = MsgAddressIntOrNone::Some(src);
+            }
+            CommonMsgInfo::ExtOutMsgInfo(header) => {
+                header.src = MsgAddressIntOrNone::Some(src);
+            }
+            _ => ()
+        };
+    }
+
+    ///
+    /// Get message's Unix time and logical time
+    /// None only for internal and external outbound message
+    ///
+    pub fn at_and_lt(&self) -> Option<(u32, u64)> {
+        match &self.header {
+            CommonMsgInfo::IntMsgInfo(header) => {
+                Some((header.created_at.as_u32(), header.created_lt))
+            },
+            CommonMsgInfo::ExtOutMsgInfo(header) => {
+                Some((header.created_at.as_u32(), header.created_lt))
+            },
+            _ => None
+        }
+    }
+
+    pub fn lt(&self) -> Option<u64> {
+        match &self.header {
+            CommonMsgInfo::IntMsgInfo(header) => {
+                Some(header.created_lt)
+            },
+            CommonMsgInfo::ExtOutMsgInfo(header) => {
+                Some(header.created_lt)
+            },
+            _ => None
+        }
+    }
+
+    pub fn bounced(&self) -> bool {
+        match &self.header {
+            CommonMsgInfo::IntMsgInfo(header) => header.bounced,
+            _ => false,
+        }
+    }
+
+    ///
+    /// Check that the message deploys account by its StateInit: destination account id
+    /// (before anycast rewrite) must be the hash of StateInit, anycast depth must be equal
+    /// to split_depth and StateInit must have code to create active account.
+    /// Returns init_code_hash to be stored in the account if CapInitCodeHash is enabled
+    /// (the same as Account::from_message_by_init_code_hash does).
+    ///
+    pub fn check_deploy(&self, caps: &CapabilitiesSet) -> Result<Option<UInt256>> {
+        let init = self.state_init().ok_or_else(|| error!(
+            BlockError::InvalidData("message has no StateInit".to_string())
+        ))?;
+        let dst = self.dst_ref().ok_or_else(|| error!(
+            BlockError::InvalidData("message has no internal destination".to_string())
+        ))?;
+        let hash = init.hash()?;
+        let (_, account_id) = dst.extract_std_address(false)?;
+        if account_id.remaining_bits() != 256 || UInt256::from_slice(&account_id.get_bytestring(0)) != hash {
+            fail!(BlockError::InvalidData(
+                format!("destination {} doesn't match StateInit hash {:x}", dst, hash)
+            ))
+        }
+        let split_depth = init.split_depth().map(|depth| depth.as_u32()).unwrap_or_default();
+        let anycast_depth = dst.rewrite_pfx().map(|anycast| anycast.depth.as_u32()).unwrap_or_default();
+        if split_depth != anycast_depth {
+            fail!(BlockError::InvalidData(format!(
+                "anycast depth {} of destination doesn't match split_depth {} of StateInit",
+                anycast_depth, split_depth
+            )))
+        }
+        let code = init.code().ok_or_else(|| error!(
+            BlockError::InvalidData("StateInit has no code".to_string())
+        ))?;
+        match caps.contains(GlobalCapabilities::CapInitCodeHash) {
+            true => Ok(Some(code.repr_hash())),
+            false => Ok(None)
+        }
+    }
+
+    ///
+    /// Hash of inbound external message which doesn't depend on the way it was relayed:
+    /// src is AddrNone, import_fee is zero, StateInit is dropped and body is stored in reference.
+    /// The same message has the same normalized hash in InMsg and Transaction.
+    ///
+    pub fn normalized_hash(&self) -> Result<UInt256> {
+        let header = match self.ext_in_header() {
+            Some(header) => ExternalInboundMessageHeader::new(MsgAddressExt::AddrNone, header.dst.clone()),
+            None => fail!(BlockError::InvalidOperation(
+                "normalized hash is defined only for inbound external messages".to_string()
+            ))
+        };
+        let mut message = Message::with_ext_in_header(header);
+        message.set_body(self.body.clone().unwrap_or_default());
+        let mut builder = BuilderData::new();
+        message.serialize_with_params(&mut builder, &Some(true), &Some(false))?;
+        Ok(builder.into_cell()?.repr_hash())
+    }
+
+    ///
+    /// Sign the body of inbound external message with the keypair:
+    /// 512-bit signature of the body hash is prepended to the body.
+    /// Signature id should be taken from signature_id() for the network.
+    ///
+    pub fn sign_body(&mut self, keypair: &ed25519_dalek::Keypair, signature_id: Option<i32>) -> Result<()> {
+        if !self.is_inbound_external() {
+            fail!(BlockError::InvalidOperation(
+                "only inbound external message body can be signed".to_string()
+            ))
+        }
+        let body = sign_body(&self.body.clone().unwrap_or_default(), keypair, signature_id)?;
+        self.set_body(body);
+        Ok(())
+    }
+
+    ///
+    /// Check the signature in the body of inbound external message
+    ///
+    pub fn verify_body(&self, public_key: &SigPubKey, signature_id: Option<i32>) -> Result<bool> {
+        if !self.is_inbound_external() {
+            fail!(BlockError::InvalidOperation(
+                "only inbound external message body can be verified".to_string()
+            ))
+        }
+        match &self.body {
+            Some(body) => verify_body(body, public_key, signature_id),
+            None => fail!(BlockError::InvalidData("message has no body".to_string()))
+        }
+    }
+
+    ///
+    /// Make bounce reply for the internal message the same way as validator does in bounce phase.
+    /// With CapBounceMsgBody body is 0xffffffff followed by first 256 bits of original body,
+    /// with CapFullBodyInBounced the whole original body is added as reference.
+    /// `value` is the value remaining after compute and action phases (not the original one),
+    /// forward fee is calculated by the fee calculator and deducted from it.
+    /// Returns None if message must not be bounced or the value doesn't cover forward fee.
+    ///
+    pub fn make_bounced(
+        &self,
+        caps: &CapabilitiesSet,
+        fees: &FeeCalculator,
+        value: CurrencyCollection,
+        now: u32,
+        lt: u64
+    ) -> Result<Option<Message>> {
+        let header = match self.int_header() {
+            Some(header) if header.bounce && !header.bounced => header,
+            _ => return Ok(None)
+        };
+        let src = match &header.src {
+            MsgAddressIntOrNone::Some(src) => src.clone(),
+            MsgAddressIntOrNone::None => return Ok(None)
+        };
+        // bounce is paid by the account, so prices of its workchain are used
+        let is_masterchain = header.dst.is_masterchain();
+        let mut bounced_header = InternalMessageHeader::with_addresses(
+            header.dst.clone(),
+            src,
+            value,
+        );
+        bounced_header.bounced = true;
+        bounced_header.created_lt = lt;
+        bounced_header.created_at = UnixTime32::new(now);
+        let mut bounced = Message::with_int_header(bounced_header);
+        if caps.contains(GlobalCapabilities::CapBounceMsgBody) {
+            let mut builder = BuilderData::new();
+            builder.append_u32(0xFFFF_FFFF)?;
+            if let Some(body) = self.body() {
+                let bits = std::cmp::min(256, body.remaining_bits());
+                builder.checked_append_references_and_data(&body.clone().get_next_slice(bits)?)?;
+                if caps.contains(GlobalCapabilities::CapFullBodyInBounced) {
+                    builder.checked_append_reference(body.into_cell())?;
+                }
+            }
+            bounced.set_body(SliceData::load_builder(builder)?);
+        }
+
+        // root cell is not counted so fee doesn't depend on header fields
+        let msg_fees = fees.msg_fees_for_cell(&bounced.serialize()?, is_masterchain)?;
+        if let Some(header) = bounced.int_header_mut() {
+            if header.value.grams < msg_fees.fwd_fee {
+                return Ok(None)
+            }
+            header.value.grams.sub(&msg_fees.fwd_fee)?;
+            header.fwd_fee = msg_fees.remaining_fee;
+        }
+        Ok(Some(bounced))
+    }
+
     ///
     /// Get value transmitted by the message
     ///
@@ -1639,7 +2316,7 @@ impl fmt::Display for TickTock {
 }
 
 /// simple_lib$_ public:Bool root:^Cell = SimpleLib;
-#[derive(Default, Clone)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct SimpleLib {
     pub public: bool,
     pub root: Cell,
@@ -1732,6 +2409,47 @@ impl StateInit {
         self.library.set(&code.repr_hash(), &SimpleLib::new(code, public))?;
         Ok(())
     }
+
+    ///
+    /// Address of the account deployed by the state init into the workchain.
+    /// Account id is the hash of the state init. If split_depth is set, the address
+    /// has anycast info with the rewrite prefix equal to the first bits of account id.
+    ///
+    pub fn derive_address(&self, workchain_id: i32) -> Result<MsgAddressInt> {
+        let account_id = SliceData::load_builder(self.hash()?.write_to_new_cell()?)?;
+        let depth = self.split_depth.as_ref().map(|depth| depth.as_usize()).unwrap_or_default();
+        match depth {
+            0 => Self::make_address(workchain_id, None, account_id),
+            depth => {
+                let rewrite_pfx = account_id.clone().get_next_slice(depth)?;
+                let anycast = AnycastInfo::with_rewrite_pfx(rewrite_pfx)?;
+                Self::make_address(workchain_id, Some(anycast), account_id)
+            }
+        }
+    }
+
+    ///
+    /// Address of the account deployed by the state init with split_depth
+    /// into the shard selected by rewrite prefix of split_depth bits
+    ///
+    pub fn derive_anycast_address(&self, workchain_id: i32, rewrite_pfx: SliceData) -> Result<MsgAddressInt> {
+        let depth = self.split_depth.as_ref().map(|depth| depth.as_usize()).unwrap_or_default();
+        if depth == 0 || rewrite_pfx.remaining_bits() != depth {
+            fail!(BlockError::InvalidArg(format!(
+                "rewrite prefix must have {} bits (split_depth of state init), not {}",
+                depth, rewrite_pfx.remaining_bits()
+            )))
+        }
+        let account_id = SliceData::load_builder(self.hash()?.write_to_new_cell()?)?;
+        Self::make_address(workchain_id, Some(AnycastInfo::with_rewrite_pfx(rewrite_pfx)?), account_id)
+    }
+
+    fn make_address(workchain_id: i32, anycast: Option<AnycastInfo>, account_id: AccountId) -> Result<MsgAddressInt> {
+        match i8::try_from(workchain_id) {
+            Ok(workchain_id) => MsgAddressInt::with_standart(anycast, workchain_id, account_id),
+            Err(_) => MsgAddressInt::with_variant(anycast, workchain_id, account_id)
+        }
+    }
 }
 
 impl Serializable for StateInit {
@@ -1797,6 +2515,9 @@ pub struct AnycastInfo {
 impl Deserializable for AnycastInfo {
     fn read_from(&mut self, cell: &mut SliceData) -> Result<()> {
         self.depth.read_from(cell)?;
+        if self.depth.as_usize() == 0 || self.depth.as_usize() > Self::MAX_DEPTH {
+            fail!(BlockError::InvalidData(format!("wrong anycast depth {}", self.depth)))
+        }
         self.rewrite_pfx = cell.get_next_slice(self.depth.as_usize())?;
         Ok(())
     }
@@ -1924,6 +2645,24 @@ impl MsgAddress {
     }
 }
 
+impl From<MsgAddressInt> for MsgAddress {
+    fn from(address: MsgAddressInt) -> Self {
+        match address {
+            MsgAddressInt::AddrStd(addr) => MsgAddress::AddrStd(addr),
+            MsgAddressInt::AddrVar(addr) => MsgAddress::AddrVar(addr),
+        }
+    }
+}
+
+impl From<MsgAddressExt> for MsgAddress {
+    fn from(address: MsgAddressExt) -> Self {
+        match address {
+            MsgAddressExt::AddrNone => MsgAddress::AddrNone,
+            MsgAddressExt::AddrExtern(addr) => MsgAddress::AddrExt(addr),
+        }
+    }
+}
+
 impl Deserializable for MsgAddress {
     fn read_from(&mut self, cell: &mut SliceData) -> Result<()> {
         let bits = cell.get_next_bits(2)?[0] >> 6;
@@ -1948,3 +2687,7 @@ impl Deserializable for MsgAddress {
         Ok(())
     }
 }
+
+#[cfg(test)]
+#[path = "tests/test_messages.rs"]
+mod tests;
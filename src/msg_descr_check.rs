@@ -0,0 +1,329 @@
+/*
+* Copyright 2018-2020 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use std::{collections::BTreeMap, fmt};
+
+use ton_types::{Cell, Result, UInt256};
+
+use crate::{
+    blocks::Block,
+    envelope_message::MsgEnvelope,
+    hashmapaug::HashmapAugType,
+    inbound_messages::{InMsg, InMsgDescr},
+    outbound_messages::{OutMsg, OutMsgDescr, OutMsgQueue, OutMsgQueueKey},
+    transactions::Transaction,
+    Deserializable, Serializable,
+};
+
+///
+/// Kind of broken link between InMsgDescr, OutMsgDescr, transactions and queue
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MsgLinkError {
+    /// key of the descr entry is not the hash of its message
+    WrongKey(UInt256),
+    /// transaction of the descr entry is not in ShardAccountBlocks of the block
+    TransactionNotFound,
+    /// transaction of InMsgDescr entry has another inbound message
+    WrongInMsg,
+    /// transaction of OutMsgDescr entry has no such outbound message
+    WrongOutMsg,
+    /// inbound message of the transaction has no InMsgDescr entry with this transaction
+    NoInMsgDescr,
+    /// outbound message of the transaction has no OutMsgDescr entry with this transaction
+    NoOutMsgDescr,
+    /// transit message has no pair in other descr
+    TransitNotPaired,
+    /// immediately processed message has no pair in other descr
+    ReimportNotPaired,
+    /// dequeued message is absent in the queue of previous state
+    NotInQueue,
+    /// dequeued envelope differs from the one in the queue of previous state
+    WrongEnvelope,
+}
+
+impl fmt::Display for MsgLinkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MsgLinkError::WrongKey(hash) => write!(f, "descr key doesn't match message hash {:x}", hash),
+            MsgLinkError::TransactionNotFound => write!(f, "transaction is not found in the block"),
+            MsgLinkError::WrongInMsg => write!(f, "transaction has another inbound message"),
+            MsgLinkError::WrongOutMsg => write!(f, "transaction has no such outbound message"),
+            MsgLinkError::NoInMsgDescr => write!(f, "inbound message of transaction is absent in InMsgDescr"),
+            MsgLinkError::NoOutMsgDescr => write!(f, "outbound message of transaction is absent in OutMsgDescr"),
+            MsgLinkError::TransitNotPaired => write!(f, "transit message has no pair"),
+            MsgLinkError::ReimportNotPaired => write!(f, "reimported message has no pair"),
+            MsgLinkError::NotInQueue => write!(f, "dequeued message is absent in the queue"),
+            MsgLinkError::WrongEnvelope => write!(f, "dequeued envelope differs from enqueued one"),
+        }
+    }
+}
+
+///
+/// Broken link of the message with hash msg_hash
+/// (transaction_lt is set if the link involves a transaction)
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BrokenMsgLink {
+    pub msg_hash: UInt256,
+    pub transaction_lt: Option<u64>,
+    pub error: MsgLinkError,
+}
+
+impl fmt::Display for BrokenMsgLink {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "message {:x}", self.msg_hash)?;
+        if let Some(lt) = self.transaction_lt {
+            write!(f, " transaction lt {}", lt)?;
+        }
+        write!(f, ": {}", self.error)
+    }
+}
+
+// inbound message of transit or immediately processed message must be reimported by out_msg
+fn is_pair(in_msg: &InMsg, out_msg: &OutMsg) -> bool {
+    match (in_msg, out_msg) {
+        (InMsg::Transit(_), OutMsg::Transit(_) | OutMsg::TransitRequeued(_)) => true,
+        #[cfg(feature = "ton")]
+        (InMsg::DeferredTransit(_), OutMsg::DeferredTransit(_)) => true,
+        (InMsg::Immediate(_), OutMsg::Immediate(_) | OutMsg::DequeueImmediate(_)) => true,
+        _ => false
+    }
+}
+
+// messages are enqueued by the prefix of their next hop
+fn queue_key(env: &MsgEnvelope, msg_hash: &UInt256) -> Result<OutMsgQueueKey> {
+    let (_, next_prefix) = env.calc_cur_next_prefix()?;
+    Ok(OutMsgQueueKey::with_account_prefix(&next_prefix, msg_hash.clone()))
+}
+
+struct TransactionLinks {
+    lt: u64,
+    in_msg: Option<UInt256>,
+    out_msgs: Vec<UInt256>,
+}
+
+struct MsgDescrChecker<'a> {
+    in_msg_descr: InMsgDescr,
+    out_msg_descr: OutMsgDescr,
+    prev_queue: Option<&'a OutMsgQueue>,
+    // transactions of the block by their hashes
+    transactions: BTreeMap<UInt256, TransactionLinks>,
+    broken: Vec<BrokenMsgLink>,
+}
+
+impl<'a> MsgDescrChecker<'a> {
+    fn with_block(block: &Block, prev_queue: Option<&'a OutMsgQueue>) -> Result<Self> {
+        let extra = block.read_extra()?;
+        let mut transactions = BTreeMap::new();
+        extra.read_account_blocks()?.iterate_objects(|account_block| {
+            account_block.transaction_iterate_full(|lt, cell, _fees| {
+                let transaction = Transaction::construct_from_cell(cell.clone())?;
+                let mut out_msgs = Vec::new();
+                transaction.out_msgs.iterate_slices(|msg| {
+                    out_msgs.push(msg.reference(0)?.repr_hash());
+                    Ok(true)
+                })?;
+                let links = TransactionLinks {
+                    lt,
+                    in_msg: transaction.in_msg_cell().map(|cell| cell.repr_hash()),
+                    out_msgs,
+                };
+                transactions.insert(cell.repr_hash(), links);
+                Ok(true)
+            })
+        })?;
+        Ok(Self {
+            in_msg_descr: extra.read_in_msg_descr()?,
+            out_msg_descr: extra.read_out_msg_descr()?,
+            prev_queue,
+            transactions,
+            broken: Vec::new(),
+        })
+    }
+
+    fn report(&mut self, msg_hash: &UInt256, transaction_lt: Option<u64>, error: MsgLinkError) {
+        self.broken.push(BrokenMsgLink { msg_hash: msg_hash.clone(), transaction_lt, error });
+    }
+
+    // returns lt of the transaction if it is in the block
+    fn find_transaction(&mut self, msg_hash: &UInt256, transaction: &Cell) -> Option<u64> {
+        let lt = self.transactions.get(&transaction.repr_hash()).map(|links| links.lt);
+        if lt.is_none() {
+            self.report(msg_hash, None, MsgLinkError::TransactionNotFound);
+        }
+        lt
+    }
+
+    fn check_in_msg(&mut self, key: &UInt256, in_msg: &InMsg) -> Result<()> {
+        let msg_hash = in_msg.message_cell()?.repr_hash();
+        if &msg_hash != key {
+            self.report(key, None, MsgLinkError::WrongKey(msg_hash.clone()));
+        }
+        if let Some(transaction) = in_msg.transaction_cell() {
+            if let Some(lt) = self.find_transaction(&msg_hash, &transaction) {
+                if self.transactions[&transaction.repr_hash()].in_msg.as_ref() != Some(&msg_hash) {
+                    self.report(&msg_hash, Some(lt), MsgLinkError::WrongInMsg);
+                }
+            }
+        }
+        let error = match in_msg {
+            InMsg::Transit(_) => MsgLinkError::TransitNotPaired,
+            #[cfg(feature = "ton")]
+            InMsg::DeferredTransit(_) => MsgLinkError::TransitNotPaired,
+            InMsg::Immediate(_) => MsgLinkError::ReimportNotPaired,
+            _ => return Ok(())
+        };
+        let in_msg_hash = in_msg.serialize()?.repr_hash();
+        let is_paired = match self.out_msg_descr.get(key)? {
+            Some(out_msg) => is_pair(in_msg, &out_msg)
+                && out_msg.reimport_cell().map(|cell| cell.repr_hash()) == Some(in_msg_hash),
+            None => false
+        };
+        if !is_paired {
+            self.report(&msg_hash, None, error);
+        }
+        Ok(())
+    }
+
+    fn check_out_msg(&mut self, key: &UInt256, out_msg: &OutMsg) -> Result<()> {
+        // short dequeue record has no message, so its key is the only message hash
+        if !matches!(out_msg, OutMsg::DequeueShort(_)) {
+            let msg_hash = out_msg.read_message_hash()?;
+            if &msg_hash != key {
+                self.report(key, None, MsgLinkError::WrongKey(msg_hash));
+            }
+        }
+        if let Some(transaction) = out_msg.transaction_cell() {
+            if let Some(lt) = self.find_transaction(key, &transaction) {
+                let msg_hash = match out_msg.message_cell()? {
+                    Some(cell) => cell.repr_hash(),
+                    None => key.clone()
+                };
+                if !self.transactions[&transaction.repr_hash()].out_msgs.contains(&msg_hash) {
+                    self.report(key, Some(lt), MsgLinkError::WrongOutMsg);
+                }
+            }
+        }
+        if let Some(reimport) = out_msg.reimport_cell() {
+            let error = match out_msg {
+                OutMsg::Immediate(_) | OutMsg::DequeueImmediate(_) => MsgLinkError::ReimportNotPaired,
+                _ => MsgLinkError::TransitNotPaired
+            };
+            let is_paired = match self.in_msg_descr.get(key)? {
+                Some(in_msg) => in_msg.serialize()?.repr_hash() == reimport.repr_hash(),
+                None => false
+            };
+            if !is_paired {
+                self.report(key, None, error);
+            }
+        }
+        self.check_dequeue(key, out_msg)
+    }
+
+    fn check_dequeue(&mut self, key: &UInt256, out_msg: &OutMsg) -> Result<()> {
+        let queue = match self.prev_queue {
+            Some(queue) => queue,
+            None => return Ok(())
+        };
+        let (queue_key, env_hash) = match out_msg {
+            OutMsg::Dequeue(x) => {
+                (queue_key(&x.read_out_message()?, key)?, out_msg.envelope_message_hash())
+            }
+            // message from the queue delivered immediately to the same shard
+            OutMsg::DequeueImmediate(x) => {
+                (queue_key(&x.read_out_message()?, key)?, out_msg.envelope_message_hash())
+            }
+            // transit message from the queue enqueued again with new envelope,
+            // the queue contains envelope of the imported message
+            OutMsg::TransitRequeued(x) => match x.read_imported()?.in_msg_envelope_cell() {
+                Some(env_cell) => {
+                    let env = MsgEnvelope::construct_from_cell(env_cell.clone())?;
+                    (queue_key(&env, key)?, Some(env_cell.repr_hash()))
+                }
+                None => return Ok(())
+            }
+            OutMsg::DequeueShort(x) => {
+                let queue_key = OutMsgQueueKey::with_workchain_id_and_prefix(
+                    x.next_workchain, x.next_addr_pfx, key.clone()
+                );
+                (queue_key, out_msg.envelope_message_hash())
+            }
+            _ => return Ok(())
+        };
+        match queue.get(&queue_key)? {
+            Some(enqueued) => {
+                if Some(enqueued.out_msg_cell().repr_hash()) != env_hash {
+                    self.report(key, None, MsgLinkError::WrongEnvelope);
+                }
+            }
+            None => self.report(key, None, MsgLinkError::NotInQueue)
+        }
+        Ok(())
+    }
+
+    // every message of transactions must be registered in descrs with the same transaction
+    fn check_transactions(&mut self) -> Result<()> {
+        let mut broken = Vec::new();
+        for (hash, links) in &self.transactions {
+            if let Some(msg_hash) = &links.in_msg {
+                let transaction = self.in_msg_descr.get(msg_hash)?
+                    .and_then(|in_msg| in_msg.transaction_cell());
+                if transaction.map(|cell| cell.repr_hash()).as_ref() != Some(hash) {
+                    broken.push((msg_hash.clone(), links.lt, MsgLinkError::NoInMsgDescr));
+                }
+            }
+            for msg_hash in &links.out_msgs {
+                let transaction = self.out_msg_descr.get(msg_hash)?
+                    .and_then(|out_msg| out_msg.transaction_cell());
+                if transaction.map(|cell| cell.repr_hash()).as_ref() != Some(hash) {
+                    broken.push((msg_hash.clone(), links.lt, MsgLinkError::NoOutMsgDescr));
+                }
+            }
+        }
+        for (msg_hash, lt, error) in broken {
+            self.report(&msg_hash, Some(lt), error);
+        }
+        Ok(())
+    }
+
+    fn check(mut self) -> Result<Vec<BrokenMsgLink>> {
+        let in_msg_descr = self.in_msg_descr.clone();
+        in_msg_descr.iterate_with_keys(|key: UInt256, in_msg| {
+            self.check_in_msg(&key, &in_msg)?;
+            Ok(true)
+        })?;
+        let out_msg_descr = self.out_msg_descr.clone();
+        out_msg_descr.iterate_with_keys(|key: UInt256, out_msg| {
+            self.check_out_msg(&key, &out_msg)?;
+            Ok(true)
+        })?;
+        self.check_transactions()?;
+        Ok(self.broken)
+    }
+}
+
+impl Block {
+    ///
+    /// Check that InMsgDescr, OutMsgDescr and transactions of the block refer to each other.
+    /// If queue of the previous state is given, dequeued messages are checked against it.
+    /// Returns all broken links (empty if the block is consistent).
+    ///
+    pub fn check_msg_descrs(&self, prev_queue: Option<&OutMsgQueue>) -> Result<Vec<BrokenMsgLink>> {
+        MsgDescrChecker::with_block(self, prev_queue)?.check()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/test_msg_descr_check.rs"]
+mod tests;
@@ -19,7 +19,7 @@ use crate::{
     inbound_messages::InMsg,
     messages::{CommonMsgInfo, Message},
     miscellaneous::{IhrPendingInfo, ProcessedInfo},
-    shard::{AccountIdPrefixFull, ShardState},
+    shard::{AccountIdPrefixFull, ShardIdent, ShardState},
     types::{AddSub, ChildCell, CurrencyCollection},
     transactions::Transaction,
     Serializable, Deserializable, ShardStateUnsplit, MerkleProof, MerkleUpdate, OutQueueUpdate,
@@ -326,6 +326,23 @@ impl OutMsgQueueInfo {
         &self.ihr_pending
     }
 
+    /// Part of the queue info for the child shard after split:
+    /// enqueued messages stay in the shard of their current address,
+    /// pending IHR messages are filtered by shard, processed info is kept as is
+    pub fn split_for(&self, shard: &ShardIdent) -> Result<Self> {
+        let mut out_queue = OutMsgQueue::default();
+        self.out_queue.iterate_with_keys_and_aug(|key, enq, lt| {
+            let (cur_prefix, _) = enq.read_out_msg()?.calc_cur_next_prefix()?;
+            if shard.contains_full_prefix(&cur_prefix) {
+                out_queue.set(&key, &enq, &lt)?;
+            }
+            Ok(true)
+        })?;
+        let mut ihr_pending = self.ihr_pending.clone();
+        ihr_pending.split_inplace(&shard.shard_key(false))?;
+        Ok(Self::with_params(out_queue, self.proc_info.clone(), ihr_pending))
+    }
+
     pub fn merge_with(&mut self, other: &Self) -> Result<bool> {
         let mut result = self.out_queue.combine_with(&other.out_queue)?;
         if result {
@@ -0,0 +1,138 @@
+/*
+* Copyright 2018-2020 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use ton_types::{error, fail, Result};
+
+use crate::{
+    config_params::{ConfigParams, GlobalCapabilities},
+    envelope_message::{IntermediateAddress, MsgEnvelope},
+    error::BlockError,
+    master::ShardHashes,
+    messages::Message,
+    shard::{AccountIdPrefixFull, ShardIdent},
+    types::Grams,
+    Serializable,
+};
+
+///
+/// Result of routing of the message from the current address
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RouteInfo {
+    pub cur_addr: IntermediateAddress,
+    pub next_addr: IntermediateAddress,
+    /// shard where the message is now
+    pub cur_shard: ShardIdent,
+    /// shard where the message must be enqueued to (next hop)
+    pub next_shard: ShardIdent,
+}
+
+impl RouteInfo {
+    /// Message reached the shard of its destination
+    pub fn is_delivered(&self) -> bool {
+        self.cur_shard == self.next_shard
+    }
+}
+
+///
+/// Calculates routes of internal messages over the shards of the masterchain state.
+/// Hypercube routing is used unless CapOffHypercube is set: then the message
+/// goes from the source shard to the shard of destination directly.
+///
+#[derive(Clone, Debug)]
+pub struct MsgRouter<'a> {
+    shards: &'a ShardHashes,
+    off_hypercube: bool,
+}
+
+impl<'a> MsgRouter<'a> {
+    pub fn new(shards: &'a ShardHashes, off_hypercube: bool) -> Self {
+        Self { shards, off_hypercube }
+    }
+
+    pub fn with_config(shards: &'a ShardHashes, config: &ConfigParams) -> Self {
+        Self::new(shards, config.has_capability(GlobalCapabilities::CapOffHypercube))
+    }
+
+    /// Shard containing the address prefix
+    pub fn find_shard(&self, prefix: &AccountIdPrefixFull) -> Result<ShardIdent> {
+        if prefix.is_masterchain() {
+            return Ok(ShardIdent::masterchain())
+        }
+        match self.shards.find_shard_by_prefix(prefix)? {
+            Some(record) => Ok(*record.block_id.shard()),
+            None => fail!(BlockError::NotFound(format!("shard for prefix {}", prefix)))
+        }
+    }
+
+    /// Route the message from src to dest, being now at cur_addr
+    pub fn route(
+        &self,
+        src: &AccountIdPrefixFull,
+        dest: &AccountIdPrefixFull,
+        cur_addr: IntermediateAddress
+    ) -> Result<RouteInfo> {
+        let transit = src.interpolate_addr_intermediate(dest, &cur_addr)?;
+        let cur_shard = self.find_shard(&transit)?;
+        let (cur_addr, next_addr) = if !self.off_hypercube {
+            src.perform_hypercube_routing(dest, &cur_shard, cur_addr)?
+        } else if cur_shard.contains_full_prefix(dest) {
+            (IntermediateAddress::full_dest(), IntermediateAddress::full_dest())
+        } else {
+            (cur_addr, IntermediateAddress::full_dest())
+        };
+        let next_shard = self.find_shard(&src.interpolate_addr_intermediate(dest, &next_addr)?)?;
+        Ok(RouteInfo { cur_addr, next_addr, cur_shard, next_shard })
+    }
+
+    /// Route new outbound message from the shard of its source
+    pub fn route_message(&self, msg: &Message) -> Result<RouteInfo> {
+        let (src, dest) = Self::message_prefixes(msg)?;
+        self.route(&src, &dest, IntermediateAddress::full_src())
+    }
+
+    /// Envelope for new outbound message to be enqueued in the shard of its source
+    pub fn make_envelope(&self, msg: &Message, fwd_fee_remaining: Grams) -> Result<(MsgEnvelope, RouteInfo)> {
+        let route = self.route_message(msg)?;
+        let env = MsgEnvelope::with_routing(
+            msg.serialize()?,
+            fwd_fee_remaining,
+            route.cur_addr.clone(),
+            route.next_addr.clone()
+        );
+        Ok((env, route))
+    }
+
+    /// Envelope for the transit message imported into the shard of its next hop
+    pub fn next_hop(&self, env: &MsgEnvelope) -> Result<(MsgEnvelope, RouteInfo)> {
+        let (src, dest) = Self::message_prefixes(&env.read_message()?)?;
+        let route = self.route(&src, &dest, env.next_addr().clone())?;
+        let mut env = env.clone();
+        env.set_cur_addr(route.cur_addr.clone()).set_next_addr(route.next_addr.clone());
+        Ok((env, route))
+    }
+
+    fn message_prefixes(msg: &Message) -> Result<(AccountIdPrefixFull, AccountIdPrefixFull)> {
+        let src = msg.src_ref().ok_or_else(|| error!(BlockError::InvalidArg(
+            "message must have internal source address".to_string()
+        )))?;
+        let dest = msg.dst_ref().ok_or_else(|| error!(BlockError::InvalidArg(
+            "message must have internal destination address".to_string()
+        )))?;
+        Ok((AccountIdPrefixFull::checked_prefix(src)?, AccountIdPrefixFull::checked_prefix(dest)?))
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/test_routing.rs"]
+mod tests;
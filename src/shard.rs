@@ -22,7 +22,7 @@ use crate::{
     messages::MsgAddressInt,
     outbound_messages::OutMsgQueueInfo,
     shard_accounts::ShardAccounts,
-    types::{ChildCell, CurrencyCollection},
+    types::{AddSub, ChildCell, CurrencyCollection},
     validators::ValidatorSet,
     CopyleftRewards, Deserializable, IntermediateAddress, MaybeDeserialize, MaybeSerialize,
     Serializable, Account,
@@ -175,11 +175,10 @@ impl AccountIdPrefixFull {
     }
 
     /// Performs Hypercube Routing from self to dest address.
+    /// ia is the current (transit) address which must belong to cur_shard.
     /// Result: (transit_addr_dest_bits, nh_addr_dest_bits)
-    /// TBD
-    #[allow(dead_code)]
     #[allow(clippy::many_single_char_names)]
-    pub(crate) fn perform_hypercube_routing(
+    pub fn perform_hypercube_routing(
         &self,
         dest: &AccountIdPrefixFull,
         cur_shard: &ShardIdent,
@@ -509,8 +508,9 @@ impl ShardIdent {
         self.workchain_id() >= BASE_WORKCHAIN_ID && self.workchain_id() <= 255
     }
 
+    /// Check if the shard contains the address; anycast addresses are routed by rewritten prefix
     pub fn contains_address(&self, addr: &MsgAddressInt) -> Result<bool> {
-        Ok(self.workchain_id == addr.workchain_id() && self.contains_account(addr.address())?)
+        Ok(self.workchain_id == addr.workchain_id() && self.contains_account(addr.rewritten_address()?)?)
     }
     pub fn contains_account(&self, mut acc_addr: AccountId) -> Result<bool> {
         Ok(
@@ -778,6 +778,23 @@ impl ShardStateSplit {
     pub fn with_left_right(left: Cell, right: Cell) -> Self {
         ShardStateSplit { left, right }
     }
+
+    pub fn with_states(left: &ShardStateUnsplit, right: &ShardStateUnsplit) -> Result<Self> {
+        Ok(ShardStateSplit { left: left.serialize()?, right: right.serialize()? })
+    }
+
+    pub fn read_states(&self) -> Result<(ShardStateUnsplit, ShardStateUnsplit)> {
+        Ok((
+            ShardStateUnsplit::construct_from_cell(self.left.clone())?,
+            ShardStateUnsplit::construct_from_cell(self.right.clone())?
+        ))
+    }
+
+    /// Merge both states into the state of the parent shard
+    pub fn merge(&self) -> Result<ShardStateUnsplit> {
+        let (left, right) = self.read_states()?;
+        ShardStateUnsplit::merge(&left, &right)
+    }
 }
 
 impl Deserializable for ShardStateSplit {
@@ -994,6 +1011,93 @@ impl ShardStateUnsplit {
         self.write_accounts(&accounts)
     }
 
+    ///
+    /// Split the state into the states of two child shards.
+    /// Accounts, enqueued and pending IHR messages are divided by shard,
+    /// processed info and libraries are copied to both states.
+    /// Total balance of each child is the balance of its accounts; the rest of total balance
+    /// and validator fees stay in the left child. Load history is reset.
+    ///
+    pub fn split(&self) -> Result<(ShardStateUnsplit, ShardStateUnsplit)> {
+        if self.is_master_state() || self.shard_id.is_masterchain() {
+            fail!(BlockError::InvalidOperation("masterchain state can't be split".to_string()))
+        }
+        let (left_shard, right_shard) = self.shard_id.split()?;
+        let accounts = self.read_accounts()?;
+        let queue_info = self.read_out_msg_queue_info()?;
+        let mut left = self.split_part(left_shard, &accounts, &queue_info)?;
+        let mut right = self.split_part(right_shard, &accounts, &queue_info)?;
+
+        let mut rest = self.total_balance.clone();
+        if !rest.sub(accounts.full_balance())? {
+            fail!(BlockError::InvalidData(
+                "total balance of the state is less than balance of its accounts".to_string()
+            ))
+        }
+        left.total_balance.add(&rest)?;
+        right.total_validator_fees = CurrencyCollection::default();
+        Ok((left, right))
+    }
+
+    fn split_part(&self, shard: ShardIdent, accounts: &ShardAccounts, queue_info: &OutMsgQueueInfo) -> Result<Self> {
+        let accounts = accounts.with_prefix(&shard.shard_key(false))?;
+        let mut state = self.clone();
+        state.shard_id = shard;
+        state.before_split = false;
+        state.overload_history = 0;
+        state.underload_history = 0;
+        state.total_balance = accounts.full_balance().clone();
+        state.write_accounts(&accounts)?;
+        state.write_out_msg_queue_info(&queue_info.split_for(&shard)?)?;
+        Ok(state)
+    }
+
+    ///
+    /// Merge the states of left and right sibling shards into the state of their parent.
+    /// Accounts, out message queues, processed info, pending IHR messages and libraries
+    /// are combined, balances and fees are summed. Sequence numbers, time and master block
+    /// reference are taken from the latest state, min_ref_mc_seqno from the earliest one.
+    /// Load history is reset.
+    ///
+    pub fn merge(left: &ShardStateUnsplit, right: &ShardStateUnsplit) -> Result<ShardStateUnsplit> {
+        if !left.shard_id.is_left_child() || left.shard_id.sibling() != right.shard_id {
+            fail!(BlockError::InvalidArg(format!(
+                "shards {} and {} are not left and right siblings", left.shard_id, right.shard_id
+            )))
+        }
+        if left.global_id != right.global_id {
+            fail!(BlockError::InvalidData(format!(
+                "global ids of the states differ: {} and {}", left.global_id, right.global_id
+            )))
+        }
+        let mut accounts = left.read_accounts()?;
+        accounts.combine_with(&right.read_accounts()?)?;
+        accounts.update_root_extra()?;
+        let mut queue_info = left.read_out_msg_queue_info()?;
+        queue_info.merge_with(&right.read_out_msg_queue_info()?)?;
+
+        let mut state = left.clone();
+        state.shard_id = left.shard_id.merge()?;
+        state.seq_no = std::cmp::max(left.seq_no, right.seq_no);
+        state.vert_seq_no = std::cmp::max(left.vert_seq_no, right.vert_seq_no);
+        state.set_gen_time_ms(std::cmp::max(left.gen_time_ms(), right.gen_time_ms()));
+        state.gen_lt = std::cmp::max(left.gen_lt, right.gen_lt);
+        state.min_ref_mc_seqno = std::cmp::min(left.min_ref_mc_seqno, right.min_ref_mc_seqno);
+        state.before_split = false;
+        state.overload_history = 0;
+        state.underload_history = 0;
+        state.total_balance.add(&right.total_balance)?;
+        state.total_validator_fees.add(&right.total_validator_fees)?;
+        state.libraries.combine_with(&right.libraries)?;
+        let right_mc_seqno = right.master_ref.as_ref().map(|info| info.master.seq_no);
+        if right_mc_seqno > left.master_ref.as_ref().map(|info| info.master.seq_no) {
+            state.master_ref = right.master_ref.clone();
+        }
+        state.write_accounts(&accounts)?;
+        state.write_out_msg_queue_info(&queue_info)?;
+        Ok(state)
+    }
+
     pub fn overload_history(&self) -> u64 {
         self.overload_history
     }
@@ -1290,3 +1394,7 @@ impl Serializable for ShardStateUnsplit {
         Ok(())
     }
 }
+
+#[cfg(test)]
+#[path = "tests/test_shard.rs"]
+mod tests;
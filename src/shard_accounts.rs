@@ -14,11 +14,11 @@
 use crate::{
     define_HashmapAugE,
     accounts::{Account, ShardAccount},
-    hashmapaug::{Augmentable, HashmapAugType},
+    hashmapaug::{Augmentable, HashmapAugType, TraverseNextStep},
     types::{CurrencyCollection, Number5},
     Serializable, Deserializable, Augmentation,
 };
-use std::fmt;
+use std::{cmp::Ordering, fmt};
 use ton_types::{
     error, fail, Result,
     AccountId, UInt256,
@@ -66,6 +66,86 @@ impl ShardAccounts {
         self.into_subtree_with_prefix(split_key, &mut 0)?;
         self.update_root_extra()
     }
+
+    /// Accounts with the largest balances in grams sorted by balance descending.
+    /// Subtrees whose total balance is not greater than the smallest of found ones are skipped.
+    pub fn top_balances(&self, n: usize) -> Result<Vec<(UInt256, ShardAccount, DepthBalanceInfo)>> {
+        let mut top: Vec<(UInt256, ShardAccount, DepthBalanceInfo)> = Vec::with_capacity(n);
+        if n == 0 {
+            return Ok(top)
+        }
+        self.traverse(|key_prefix, _key_prefix_len, aug, value_opt| {
+            let grams = aug.balance.grams;
+            if top.len() == n && grams <= top[n - 1].2.balance.grams {
+                return Ok(TraverseNextStep::<()>::Stop)
+            }
+            match value_opt {
+                Some(account) => {
+                    let pos = top.iter().position(|(_, _, info)| info.balance.grams < grams).unwrap_or(top.len());
+                    top.insert(pos, (UInt256::from_slice(key_prefix), account, aug));
+                    top.truncate(n);
+                    Ok(TraverseNextStep::Stop)
+                }
+                None => Ok(TraverseNextStep::VisitOneZero)
+            }
+        })?;
+        Ok(top)
+    }
+
+    /// Accounts which id starts with the prefix
+    pub fn with_prefix(&self, prefix: &SliceData) -> Result<ShardAccounts> {
+        let mut accounts = self.clone();
+        accounts.into_subtree_with_prefix(prefix, &mut 0)?;
+        accounts.update_root_extra()?;
+        Ok(accounts)
+    }
+
+    /// Total balance of accounts which id starts with the prefix
+    pub fn prefix_balance(&self, prefix: &SliceData) -> Result<CurrencyCollection> {
+        Ok(self.with_prefix(prefix)?.full_balance().clone())
+    }
+
+    /// Up to limit accounts in ascending order of id starting after given account id
+    /// (from the first account if it is None). Use id of the last account to get next page.
+    pub fn page_after(&self, after: Option<&UInt256>, limit: usize) -> Result<Vec<(UInt256, ShardAccount)>> {
+        let mut page = Vec::new();
+        if limit == 0 {
+            return Ok(page)
+        }
+        self.traverse(|key_prefix, key_prefix_len, _aug, value_opt| {
+            let ordering = match after {
+                Some(after) => cmp_key_prefix(key_prefix, key_prefix_len, after.as_slice()),
+                None => Ordering::Greater
+            };
+            match (ordering, value_opt) {
+                (Ordering::Greater, Some(account)) => {
+                    page.push((UInt256::from_slice(key_prefix), account));
+                    match page.len() >= limit {
+                        true => Ok(TraverseNextStep::End(())),
+                        false => Ok(TraverseNextStep::Stop)
+                    }
+                }
+                (Ordering::Less, _) | (_, Some(_)) => Ok(TraverseNextStep::Stop),
+                (_, None) => Ok(TraverseNextStep::VisitZeroOne)
+            }
+        })?;
+        Ok(page)
+    }
+}
+
+// compare first prefix_len bits of key with the prefix
+fn cmp_key_prefix(prefix: &[u8], prefix_len: usize, key: &[u8]) -> Ordering {
+    let bytes = prefix_len / 8;
+    match prefix[..bytes].cmp(&key[..bytes]) {
+        Ordering::Equal => (),
+        ordering => return ordering
+    }
+    let bits = prefix_len % 8;
+    if bits == 0 {
+        return Ordering::Equal
+    }
+    let mask = 0xFFu8 << (8 - bits);
+    (prefix[bytes] & mask).cmp(&(key[bytes] & mask))
 }
 
 impl Augmentation<DepthBalanceInfo> for ShardAccount {
@@ -15,11 +15,12 @@ use crate::{
     define_HashmapE,
     Serializable, Deserializable,
     blocks::BlockIdExt,
+    config_params::{CapabilitiesSet, GlobalCapabilities},
     error::BlockError,
     validators::ValidatorBaseInfo,
     validators::ValidatorDescr
 };
-use ed25519::signature::Verifier;
+use ed25519::signature::{Signer, Verifier};
 use std::{
     io::{Cursor, Write},
     str::FromStr,
@@ -205,6 +206,10 @@ impl SigPubKey {
         Self::default()
     }
 
+    pub fn from_public_key(public_key: &ed25519_dalek::PublicKey) -> Self {
+        SigPubKey(UInt256::from_slice(public_key.as_bytes()))
+    }
+
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
         Ok(SigPubKey(UInt256::from_le_bytes(bytes)))
     }
@@ -266,6 +271,75 @@ impl Deserializable for SigPubKey {
     }
 }
 
+/*
+  SIGNED MESSAGE BODIES
+*/
+
+///
+/// Id prepended to the signed data if CapSignatureWithId is enabled:
+/// global id of the network (see Block::global_id)
+///
+pub fn signature_id(capabilities: &CapabilitiesSet, global_id: i32) -> Option<i32> {
+    match capabilities.contains(GlobalCapabilities::CapSignatureWithId) {
+        true => Some(global_id),
+        false => None
+    }
+}
+
+///
+/// Data to be signed for the hash: big-endian signature id followed by the hash
+/// if signature id is used and the hash itself otherwise
+///
+pub fn signature_data(hash: &UInt256, signature_id: Option<i32>) -> Vec<u8> {
+    match signature_id {
+        Some(signature_id) => {
+            let mut data = Vec::with_capacity(4 + 32);
+            data.extend_from_slice(&signature_id.to_be_bytes());
+            data.extend_from_slice(hash.as_slice());
+            data
+        }
+        None => hash.as_slice().to_vec()
+    }
+}
+
+///
+/// Sign the body: the result is the 512-bit signature followed by the body.
+/// The representation hash of the cell with the body is signed.
+///
+pub fn sign_body(
+    body: &SliceData,
+    keypair: &ed25519_dalek::Keypair,
+    signature_id: Option<i32>
+) -> Result<SliceData> {
+    let hash = body.clone().into_cell().repr_hash();
+    let signature = keypair.sign(&signature_data(&hash, signature_id));
+    let mut builder = BuilderData::new();
+    builder.append_raw(&signature.to_bytes(), ed25519_dalek::SIGNATURE_LENGTH * 8)?;
+    builder.checked_append_references_and_data(body)?;
+    SliceData::load_builder(builder)
+}
+
+///
+/// Split the signed body into the signature and the rest of the body
+///
+pub fn split_signed_body(body: &SliceData) -> Result<(CryptoSignature, SliceData)> {
+    let mut body = body.clone();
+    if body.remaining_bits() < ed25519_dalek::SIGNATURE_LENGTH * 8 {
+        fail!(BlockError::InvalidData("body is too short to have a signature".to_string()))
+    }
+    let signature = CryptoSignature::from_bytes(&body.get_next_bits(ed25519_dalek::SIGNATURE_LENGTH * 8)?)?;
+    Ok((signature, body))
+}
+
+///
+/// Check the signature in the prefix of the body against the rest of the body
+///
+pub fn verify_body(body: &SliceData, public_key: &SigPubKey, signature_id: Option<i32>) -> Result<bool> {
+    let (signature, body) = split_signed_body(body)?;
+    let hash = body.into_cell().repr_hash();
+    Ok(public_key.verify_signature(&signature_data(&hash, signature_id), &signature))
+}
+
 /*
   PROOFS
 */
@@ -347,7 +421,8 @@ impl BlockSignaturesPure {
         let mut weight = 0;
         self.signatures().iterate_slices(|ref mut _key, ref mut slice| {
             let sign = CryptoSignaturePair::construct_from(slice)?;
-            if let Some(vd) = validators_map.get(&sign.node_id_short) {
+            // validator is counted once even if its signature is repeated
+            if let Some(vd) = validators_map.remove(&sign.node_id_short) {
                 if !vd.verify_signature(data, &sign.sign) {
                     fail!(BlockError::BadSignature)
                 }
@@ -522,3 +597,7 @@ impl Deserializable for BlockProof {
         Ok(())
     }
 }
+
+#[cfg(test)]
+#[path = "tests/test_signature.rs"]
+mod tests;
@@ -0,0 +1,193 @@
+/*
+* Copyright 2018-2020 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use super::*;
+use crate::{
+    accounts::AccountStatus,
+    config_params::{BlockCreateFees, ConfigParam14, ConfigParamEnum},
+    envelope_message::MsgEnvelope,
+    inbound_messages::InMsg,
+    messages::{InternalMessageHeader, Message, MsgAddressInt},
+    outbound_messages::OutMsg,
+    transactions::Transaction,
+};
+
+const MC_BLOCK_FEE: u64 = 1_700_000_000;
+const BASECHAIN_BLOCK_FEE: u64 = 1_000_000_000;
+
+fn config() -> ConfigParams {
+    let mut config = ConfigParams::new();
+    config.set_config(ConfigParamEnum::ConfigParam14(ConfigParam14 {
+        block_create_fees: BlockCreateFees {
+            masterchain_block_fee: Grams::from(MC_BLOCK_FEE),
+            basechain_block_fee: Grams::from(BASECHAIN_BLOCK_FEE),
+        },
+    })).unwrap();
+    config
+}
+
+fn envelope(value: u64, fwd_fee: u64) -> Cell {
+    let src = MsgAddressInt::with_standart(None, 0, AccountId::from([1; 32])).unwrap();
+    let dst = MsgAddressInt::with_standart(None, 0, AccountId::from([2; 32])).unwrap();
+    let header = InternalMessageHeader::with_addresses(src, dst, CurrencyCollection::with_grams(value));
+    let msg = Message::with_int_header(header);
+    MsgEnvelope::with_message_and_fee(&msg, Grams::from(fwd_fee)).unwrap().serialize().unwrap()
+}
+
+// block with one imported message, one exported message and one transaction
+fn test_block(shard: ShardIdent) -> Block {
+    let mut tr = Transaction::with_address_and_status(AccountId::from([2; 32]), AccountStatus::AccStateActive);
+    tr.set_total_fees(CurrencyCollection::with_grams(700));
+    let tr_cell = tr.serialize().unwrap();
+
+    let mut in_msgs = InMsgDescr::default();
+    in_msgs.insert(&InMsg::final_msg(envelope(1_000_000, 1000), tr_cell.clone(), Grams::from(1000u64))).unwrap();
+    let mut out_msgs = OutMsgDescr::default();
+    out_msgs.insert(&OutMsg::new(envelope(500_000, 2000), tr_cell)).unwrap();
+    let mut account_blocks = ShardAccountBlocks::default();
+    account_blocks.add_transaction(&tr).unwrap();
+
+    let mut extra = BlockExtra::new();
+    extra.write_in_msg_descr(&in_msgs).unwrap();
+    extra.write_out_msg_descr(&out_msgs).unwrap();
+    extra.write_account_blocks(&account_blocks).unwrap();
+    let mut info = BlockInfo::new();
+    info.set_shard(shard);
+    Block::with_params(0, info, ValueFlow::default(), MerkleUpdate::default(), extra).unwrap()
+}
+
+// value flow consistent with the block
+fn balanced_value_flow(block: &Block, created: u64) -> ValueFlow {
+    let extra = block.read_extra().unwrap();
+    let import_fees = extra.read_in_msg_descr().unwrap().root_extra().clone();
+    let exported = extra.read_out_msg_descr().unwrap().root_extra().clone();
+    let mut fees_collected = extra.read_account_blocks().unwrap().root_extra().clone();
+    fees_collected.grams.add(&import_fees.fees_collected).unwrap();
+    fees_collected.grams.add(&Grams::from(created)).unwrap();
+
+    let from_prev_blk = CurrencyCollection::with_grams(1_000_000_000_000);
+    let mut to_next_blk = from_prev_blk.clone();
+    to_next_blk.add(&import_fees.value_imported).unwrap();
+    to_next_blk.grams.add(&Grams::from(created)).unwrap();
+    assert!(to_next_blk.sub(&exported).unwrap());
+    assert!(to_next_blk.sub(&fees_collected).unwrap());
+    ValueFlow {
+        from_prev_blk,
+        to_next_blk,
+        imported: import_fees.value_imported,
+        exported,
+        fees_collected,
+        created: CurrencyCollection::with_grams(created),
+        ..ValueFlow::default()
+    }
+}
+
+fn add(value: &mut CurrencyCollection, grams: u64) {
+    value.grams.add(&Grams::from(grams)).unwrap();
+}
+
+fn sub(value: &mut CurrencyCollection, grams: u64) {
+    assert!(value.grams.sub(&Grams::from(grams)).unwrap());
+}
+
+fn components(mismatches: &[ValueFlowMismatch]) -> Vec<ValueFlowComponent> {
+    mismatches.iter().map(|mismatch| mismatch.component).collect()
+}
+
+fn left_shard() -> ShardIdent {
+    ShardIdent::with_tagged_prefix(0, 0x4000_0000_0000_0000).unwrap()
+}
+
+#[test]
+fn test_value_flow_consistent() {
+    let block = test_block(left_shard());
+    let value_flow = balanced_value_flow(&block, BASECHAIN_BLOCK_FEE >> 1);
+    assert!(value_flow.check(&block).unwrap().is_empty());
+    assert!(value_flow.check_with_config(&block, &config()).unwrap().is_empty());
+}
+
+#[test]
+fn test_value_flow_broken_component() {
+    let block = test_block(left_shard());
+    let balanced = balanced_value_flow(&block, BASECHAIN_BLOCK_FEE >> 1);
+    // every change is compensated so that exactly one equation is broken
+    let cases: [(ValueFlowComponent, fn(&mut ValueFlow)); 8] = [
+        (ValueFlowComponent::Balance, |vf| add(&mut vf.to_next_blk, 1)),
+        (ValueFlowComponent::FeesCollected, |vf| {
+            add(&mut vf.fees_collected, 1);
+            sub(&mut vf.to_next_blk, 1);
+        }),
+        (ValueFlowComponent::Imported, |vf| {
+            add(&mut vf.imported, 1);
+            add(&mut vf.to_next_blk, 1);
+        }),
+        (ValueFlowComponent::Exported, |vf| {
+            add(&mut vf.exported, 1);
+            sub(&mut vf.to_next_blk, 1);
+        }),
+        (ValueFlowComponent::FeesImported, |vf| {
+            add(&mut vf.fees_imported, 1);
+            add(&mut vf.fees_collected, 1);
+        }),
+        (ValueFlowComponent::Recovered, |vf| {
+            add(&mut vf.recovered, 1);
+            add(&mut vf.to_next_blk, 1);
+        }),
+        (ValueFlowComponent::Minted, |vf| {
+            add(&mut vf.minted, 1);
+            add(&mut vf.to_next_blk, 1);
+        }),
+        (ValueFlowComponent::Created, |vf| {
+            add(&mut vf.created, 1);
+            add(&mut vf.fees_collected, 1);
+        }),
+    ];
+    for (component, change) in cases {
+        let mut value_flow = balanced.clone();
+        change(&mut value_flow);
+        let mismatches = value_flow.check_with_config(&block, &config()).unwrap();
+        assert_eq!(components(&mismatches), vec![component]);
+        assert_ne!(mismatches[0].expected, mismatches[0].actual);
+    }
+}
+
+#[test]
+fn test_value_flow_created_by_config() {
+    let config = config();
+
+    let block = test_block(ShardIdent::masterchain());
+    assert!(balanced_value_flow(&block, MC_BLOCK_FEE).check_with_config(&block, &config).unwrap().is_empty());
+    let mismatches = balanced_value_flow(&block, BASECHAIN_BLOCK_FEE).check_with_config(&block, &config).unwrap();
+    assert_eq!(components(&mismatches), vec![ValueFlowComponent::Created]);
+    assert_eq!(mismatches[0].expected, CurrencyCollection::with_grams(MC_BLOCK_FEE));
+
+    // shard block creates its part of basechain fee
+    let shards = [
+        (ShardIdent::with_workchain_id(0).unwrap(), BASECHAIN_BLOCK_FEE),
+        (left_shard(), BASECHAIN_BLOCK_FEE >> 1),
+        (ShardIdent::with_tagged_prefix(0, 0x6000_0000_0000_0000).unwrap(), BASECHAIN_BLOCK_FEE >> 2),
+    ];
+    for (shard, created) in shards {
+        let block = test_block(shard);
+        assert!(balanced_value_flow(&block, created).check_with_config(&block, &config).unwrap().is_empty());
+        let mismatches = balanced_value_flow(&block, BASECHAIN_BLOCK_FEE + 1)
+            .check_with_config(&block, &config).unwrap();
+        assert_eq!(components(&mismatches), vec![ValueFlowComponent::Created]);
+        assert_eq!(mismatches[0].expected, CurrencyCollection::with_grams(created));
+        assert_eq!(mismatches[0].actual, CurrencyCollection::with_grams(BASECHAIN_BLOCK_FEE + 1));
+    }
+
+    // without param 14 created can't be checked
+    balanced_value_flow(&block, MC_BLOCK_FEE).check_with_config(&block, &ConfigParams::new())
+        .expect_err("no param 14 must fail");
+}
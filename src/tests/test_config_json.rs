@@ -0,0 +1,390 @@
+/*
+* Copyright 2018-2020 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use super::*;
+
+fn cell(data: u32) -> Cell {
+    data.write_to_new_cell().unwrap().into_cell().unwrap()
+}
+
+fn validator_set(since: u32) -> ValidatorSet {
+    let list = vec![
+        ValidatorDescr::with_params(SigPubKey::from_bytes(&[1; 32]).unwrap(), 17, None),
+        ValidatorDescr::with_params(SigPubKey::from_bytes(&[2; 32]).unwrap(), 5, Some(UInt256::from([3; 32]))),
+    ];
+    ValidatorSet::new(since, since + 1000, 1, list).unwrap()
+}
+
+fn proposal_setup(rounds: u8) -> ConfigProposalSetup {
+    ConfigProposalSetup {
+        min_tot_rounds: rounds,
+        max_tot_rounds: rounds + 2,
+        min_wins: 2,
+        max_losses: 1,
+        min_store_sec: 1000,
+        max_store_sec: 100000,
+        bit_price: 1,
+        cell_price: 500,
+    }
+}
+
+fn gas_prices(gas_price: u64) -> GasLimitsPrices {
+    let mut prices = GasLimitsPrices {
+        gas_price,
+        gas_limit: 1_000_000,
+        special_gas_limit: 10_000_000,
+        gas_credit: 10_000,
+        block_gas_limit: 10_000_000,
+        freeze_due_limit: 100_000_000,
+        delete_due_limit: 1_000_000_000,
+        flat_gas_limit: 100,
+        flat_gas_price: 100_000,
+        max_gas_threshold: 0,
+    };
+    prices.max_gas_threshold = prices.calc_max_gas_threshold();
+    prices
+}
+
+fn block_limits(scale: u32) -> BlockLimits {
+    BlockLimits::with_limits(
+        ParamLimits::with_limits(1000 * scale, 2000 * scale, 3000 * scale).unwrap(),
+        ParamLimits::with_limits(100 * scale, 200 * scale, 300 * scale).unwrap(),
+        ParamLimits::with_limits(10 * scale, 20 * scale, 30 * scale).unwrap(),
+    )
+}
+
+fn fwd_prices(lump_price: u64) -> MsgForwardPrices {
+    MsgForwardPrices {
+        lump_price,
+        bit_price: 1 << 16,
+        cell_price: 100 << 16,
+        ihr_price_factor: 98304,
+        first_frac: 21845,
+        next_frac: 21845,
+    }
+}
+
+fn oracles() -> BridgeOracles {
+    let mut oracles = BridgeOracles::default();
+    oracles.set(&UInt256::from([7; 32]), &UInt256::from([8; 32])).unwrap();
+    oracles.set(&UInt256::from([9; 32]), &UInt256::from([10; 32])).unwrap();
+    oracles
+}
+
+fn oracle_bridge(seed: u8) -> OracleBridgeParams {
+    OracleBridgeParams {
+        bridge_address: UInt256::from([seed; 32]),
+        oracle_multisig_address: UInt256::from([seed + 1; 32]),
+        oracles: oracles(),
+        external_chain_address: UInt256::from([seed + 2; 32]),
+    }
+}
+
+fn jetton_bridge(seed: u8, fees: JettonBridgeFees) -> JettonBridgeParams {
+    JettonBridgeParams {
+        bridge_address: UInt256::from([seed; 32]),
+        oracles_address: UInt256::from([seed + 1; 32]),
+        oracles: oracles(),
+        state_flags: 1,
+        fees,
+    }
+}
+
+// one non-trivial value for every typed parameter
+fn samples() -> Vec<ConfigParamEnum> {
+    let mut to_mint = ExtraCurrencyCollection::default();
+    to_mint.set(&239u32, &VarUInteger32::from_two_u128(0, 666).unwrap()).unwrap();
+
+    let mut mandatory_params = MandatoryParams::default();
+    for index in [0u32, 1, 9, 10, 12, 14, 15, 16, 17, 34] {
+        mandatory_params.add_key(&index).unwrap();
+    }
+    let mut critical_params = MandatoryParams::default();
+    for index in [0u32, 1, 9, 10] {
+        critical_params.add_key(&index).unwrap();
+    }
+
+    let mut workchain = WorkchainDescr {
+        enabled_since: 1_573_821_854,
+        active: true,
+        accept_msgs: true,
+        zerostate_root_hash: UInt256::from([4; 32]),
+        zerostate_file_hash: UInt256::from([5; 32]),
+        format: WorkchainFormat::Basic(WorkchainFormat1 { vm_version: 0, vm_mode: 0 }),
+        ..WorkchainDescr::default()
+    };
+    workchain.set_min_split(2).unwrap();
+    workchain.set_max_split(32).unwrap();
+    workchain.set_actual_min_split(2).unwrap();
+    let mut param12 = ConfigParam12::new();
+    param12.insert(0, &workchain).unwrap();
+
+    let mut param18 = ConfigParam18::default();
+    param18.insert(&StoragePrices {
+        utime_since: 0,
+        bit_price_ps: 1,
+        cell_price_ps: 500,
+        mc_bit_price_ps: 1000,
+        mc_cell_price_ps: 500000,
+    }).unwrap();
+
+    let mut param31 = ConfigParam31::new();
+    param31.add_address(UInt256::from([0x33; 32]));
+    param31.add_address(UInt256::from([0x55; 32]));
+
+    let mut param39 = ConfigParam39::new();
+    let temp_key = ValidatorTempKey::with_params(
+        UInt256::from([6; 32]), SigPubKey::from_bytes(&[7; 32]).unwrap(), 3, 1_600_000_000
+    );
+    param39.insert(
+        &UInt256::from([8; 32]),
+        &ValidatorSignedTempKey::with_key_and_signature(temp_key, CryptoSignature::default())
+    ).unwrap();
+
+    let mut license_rates = LicenseRates::default();
+    license_rates.set(&0u8, &50u8).unwrap();
+    license_rates.set(&1u8, &10u8).unwrap();
+
+    let mut suspended = SuspendedAddresses::default();
+    suspended.add_suspended_address(0, UInt256::from([0x77; 32])).unwrap();
+    suspended.add_suspended_address(-1, UInt256::from([0x78; 32])).unwrap();
+
+    let mut precompiled = PrecompiledContractsConfig::new();
+    precompiled.insert(&UInt256::from([0x99; 32]), &PrecompiledSmc::with_gas_usage(1000)).unwrap();
+
+    vec![
+        ConfigParamEnum::ConfigParam0(ConfigParam0 { config_addr: UInt256::from([0x10; 32]) }),
+        ConfigParamEnum::ConfigParam1(ConfigParam1 { elector_addr: UInt256::from([0x11; 32]) }),
+        ConfigParamEnum::ConfigParam2(ConfigParam2 { minter_addr: UInt256::from([0x12; 32]) }),
+        ConfigParamEnum::ConfigParam3(ConfigParam3 { fee_collector_addr: UInt256::from([0x13; 32]) }),
+        ConfigParamEnum::ConfigParam4(ConfigParam4 { dns_root_addr: UInt256::from([0x14; 32]) }),
+        ConfigParamEnum::ConfigParam5(ConfigParam5 { owner_addr: UInt256::from([0x15; 32]) }),
+        ConfigParamEnum::ConfigParam6(ConfigParam6 {
+            mint_new_price: Grams::from(1_000_000_000u64),
+            mint_add_price: Grams::from(100_000_000u64),
+        }),
+        ConfigParamEnum::ConfigParam7(ConfigParam7 { to_mint }),
+        ConfigParamEnum::ConfigParam8(ConfigParam8 {
+            global_version: GlobalVersion { version: 45, capabilities: 0x2_0000_046e },
+        }),
+        ConfigParamEnum::ConfigParam9(ConfigParam9 { mandatory_params }),
+        ConfigParamEnum::ConfigParam10(ConfigParam10 { critical_params }),
+        ConfigParamEnum::ConfigParam11(ConfigVotingSetup::new(&proposal_setup(2), &proposal_setup(6)).unwrap()),
+        ConfigParamEnum::ConfigParam12(param12),
+        ConfigParamEnum::ConfigParam13(ConfigParam13 { cell: cell(0xdead_beef) }),
+        ConfigParamEnum::ConfigParam14(ConfigParam14 {
+            block_create_fees: BlockCreateFees {
+                masterchain_block_fee: Grams::from(1_700_000_000u64),
+                basechain_block_fee: Grams::from(1_000_000_000u64),
+            },
+        }),
+        ConfigParamEnum::ConfigParam15(ConfigParam15 {
+            validators_elected_for: 65536,
+            elections_start_before: 32768,
+            elections_end_before: 8192,
+            stake_held_for: 32768,
+        }),
+        ConfigParamEnum::ConfigParam16(ConfigParam16 {
+            max_validators: Number16::new(1000).unwrap(),
+            max_main_validators: Number16::new(100).unwrap(),
+            min_validators: Number16::new(13).unwrap(),
+        }),
+        ConfigParamEnum::ConfigParam17(ConfigParam17 {
+            min_stake: Grams::from(10_000_000_000_000u64),
+            max_stake: Grams::from(10_000_000_000_000_000u64),
+            min_total_stake: Grams::from(100_000_000_000_000u64),
+            max_stake_factor: 196608,
+        }),
+        ConfigParamEnum::ConfigParam18(param18),
+        ConfigParamEnum::ConfigParam19(ConfigParam19 { global_id: -239 }),
+        ConfigParamEnum::ConfigParam20(gas_prices(655_360_000)),
+        ConfigParamEnum::ConfigParam21(gas_prices(65_536_000)),
+        ConfigParamEnum::ConfigParam22(block_limits(10)),
+        ConfigParamEnum::ConfigParam23(block_limits(1)),
+        ConfigParamEnum::ConfigParam24(fwd_prices(10_000_000)),
+        ConfigParamEnum::ConfigParam25(fwd_prices(1_000_000)),
+        ConfigParamEnum::ConfigParam26(ConfigParam26::with_cell(cell(26))),
+        ConfigParamEnum::ConfigParam27(ConfigParam27::with_cell(cell(27))),
+        ConfigParamEnum::ConfigParam28(CatchainConfig {
+            isolate_mc_validators: false,
+            shuffle_mc_validators: true,
+            mc_catchain_lifetime: 250,
+            shard_catchain_lifetime: 250,
+            shard_validators_lifetime: 1000,
+            shard_validators_num: 7,
+        }),
+        ConfigParamEnum::ConfigParam29(ConfigParam29 {
+            consensus_config: ConsensusConfig {
+                new_catchain_ids: true,
+                round_candidates: 3,
+                next_candidate_delay_ms: 2000,
+                consensus_timeout_ms: 16000,
+                fast_attempts: 3,
+                attempt_duration: 8,
+                catchain_max_deps: 4,
+                max_block_bytes: 2 << 20,
+                max_collated_bytes: 2 << 20,
+            },
+        }),
+        ConfigParamEnum::ConfigParam30(DelectorParams {
+            delections_step: 10,
+            validator_init_code_hash: UInt256::from([0x30; 32]),
+            staker_init_code_hash: UInt256::from([0x31; 32]),
+        }),
+        ConfigParamEnum::ConfigParam31(param31),
+        ConfigParamEnum::ConfigParam32(ConfigParam32 { prev_validators: validator_set(100) }),
+        ConfigParamEnum::ConfigParam33(ConfigParam33 { prev_temp_validators: validator_set(200) }),
+        ConfigParamEnum::ConfigParam34(ConfigParam34 { cur_validators: validator_set(300) }),
+        ConfigParamEnum::ConfigParam35(ConfigParam35 { cur_temp_validators: validator_set(400) }),
+        ConfigParamEnum::ConfigParam36(ConfigParam36 { next_validators: validator_set(500) }),
+        ConfigParamEnum::ConfigParam37(ConfigParam37 { next_temp_validators: validator_set(600) }),
+        ConfigParamEnum::ConfigParam38(ConfigParam38::with_cell(cell(38))),
+        ConfigParamEnum::ConfigParam39(param39),
+        ConfigParamEnum::ConfigParam40(ConfigParam40 { slashing_config: SlashingConfig::new() }),
+        ConfigParamEnum::ConfigParam41(ConfigParam41::with_cell(cell(41))),
+        ConfigParamEnum::ConfigParam42(ConfigCopyleft {
+            copyleft_reward_threshold: Grams::from(100_000_000_000u64),
+            license_rates,
+        }),
+        ConfigParamEnum::ConfigParam43(SizeLimitsConfig {
+            max_msg_bits: 1 << 21,
+            max_msg_cells: 1 << 13,
+            max_library_cells: 1000,
+            max_vm_data_depth: 512,
+            max_ext_msg_size: 65535,
+            max_ext_msg_depth: 512,
+            acc_state_limits: Some(SizeLimitsAccState {
+                max_acc_state_cells: 1 << 16,
+                max_acc_state_bits: 1 << 25,
+                max_acc_public_libraries: 256,
+                defer_out_queue_size_limit: 256,
+            }),
+        }),
+        ConfigParamEnum::ConfigParam44(suspended),
+        ConfigParamEnum::ConfigParam45(precompiled),
+        ConfigParamEnum::ConfigParam71(oracle_bridge(0x71)),
+        ConfigParamEnum::ConfigParam72(oracle_bridge(0x72)),
+        ConfigParamEnum::ConfigParam73(oracle_bridge(0x73)),
+        ConfigParamEnum::ConfigParam79(jetton_bridge(0x79, JettonBridgeFees::V0 {
+            burn_bridge_fee: Grams::from(1_000_000u64),
+        })),
+        ConfigParamEnum::ConfigParam80(jetton_bridge(0x80, JettonBridgeFees::V0 {
+            burn_bridge_fee: Grams::zero(),
+        })),
+        ConfigParamEnum::ConfigParam81(jetton_bridge(0x81, JettonBridgeFees::V1 {
+            prices: JettonBridgePrices {
+                bridge_burn_fee: Grams::from(1u64),
+                bridge_mint_fee: Grams::from(2u64),
+                wallet_min_tons_for_storage: Grams::from(3u64),
+                wallet_gas_consumption: Grams::from(4u64),
+                minter_min_tons_for_storage: Grams::from(5u64),
+                discover_gas_consumption: Grams::from(6u64),
+            },
+            external_chain_address: UInt256::from([0x82; 32]),
+        })),
+        ConfigParamEnum::ConfigParam82(jetton_bridge(0x83, JettonBridgeFees::V1 {
+            prices: JettonBridgePrices::default(),
+            external_chain_address: UInt256::default(),
+        })),
+    ]
+}
+
+fn param_cell(config: &ConfigParams, index: u32) -> Cell {
+    let key = SliceData::load_builder(index.write_to_new_cell().unwrap()).unwrap();
+    config.config_params.get(key).unwrap().unwrap().reference(0).unwrap()
+}
+
+fn params_hash(config: &ConfigParams) -> Option<UInt256> {
+    config.config_params.data().map(|root| root.repr_hash())
+}
+
+fn round_trip(config: &ConfigParams) -> ConfigParams {
+    ConfigParams::from_json_str(&config.to_json_string().unwrap()).unwrap()
+}
+
+#[test]
+fn test_typed_params_round_trip() {
+    let mut all = ConfigParams::new();
+    all.config_addr = UInt256::from([0x55; 32]);
+    for param in samples() {
+        let mut config = ConfigParams::new();
+        config.set_config(param.clone()).unwrap();
+        all.set_config(param.clone()).unwrap();
+        let index = param.write_to_cell(&mut BuilderData::new()).unwrap();
+
+        // typed value serializes back into the same cell, so original boc is not needed
+        let json = config.to_json().unwrap();
+        let entry = &json["params"][index.to_string()];
+        assert!(entry.get("value").is_some(), "param {} has no value", index);
+        assert!(entry.get("boc").is_none(), "param {} is not typed losslessly", index);
+
+        let imported = round_trip(&config);
+        assert_eq!(param_cell(&imported, index).repr_hash(), param_cell(&config, index).repr_hash(), "param {}", index);
+        assert_eq!(params_hash(&imported), params_hash(&config), "param {}", index);
+        assert_eq!(imported.config(index).unwrap(), Some(param));
+    }
+
+    let imported = round_trip(&all);
+    assert_eq!(imported.config_addr, all.config_addr);
+    assert_eq!(params_hash(&imported), params_hash(&all));
+}
+
+#[test]
+fn test_unknown_param_round_trip() {
+    let mut builder = 0x1234_5678u32.write_to_new_cell().unwrap();
+    builder.checked_append_reference(cell(0xabcd)).unwrap();
+    let param = ConfigParamEnum::ConfigParamAny(50, SliceData::load_builder(builder).unwrap());
+    let mut config = ConfigParams::new();
+    config.set_config(param).unwrap();
+
+    let json = config.to_json().unwrap();
+    let entry = &json["params"]["50"];
+    assert!(entry.get("value").is_none());
+    assert!(entry.get("boc").is_some());
+
+    let imported = round_trip(&config);
+    assert_eq!(param_cell(&imported, 50).repr_hash(), param_cell(&config, 50).repr_hash());
+    assert_eq!(params_hash(&imported), params_hash(&config));
+}
+
+// gas prices with legacy constructor #dd are read fine, but written with #de
+#[test]
+fn test_legacy_param_keeps_original_cell() {
+    let mut builder = 0xddu8.write_to_new_cell().unwrap();
+    for value in [655_360_000u64, 1_000_000, 10_000, 10_000_000, 100_000_000, 1_000_000_000] {
+        value.write_to(&mut builder).unwrap();
+    }
+    let legacy = builder.into_cell().unwrap();
+    let typed = ConfigParamEnum::construct_from_cell_and_number(legacy.clone(), 20).unwrap();
+    assert_ne!(typed.param_cell().unwrap().repr_hash(), legacy.repr_hash());
+
+    let mut config = ConfigParams::new();
+    config.set_config(ConfigParamEnum::ConfigParamAny(20, SliceData::load_cell(legacy.clone()).unwrap())).unwrap();
+
+    let mut json = config.to_json().unwrap();
+    let entry = &json["params"]["20"];
+    assert!(entry.get("value").is_some());
+    assert!(entry.get("boc").is_some());
+
+    // untouched value keeps original cell
+    let imported = ConfigParams::from_json(&json).unwrap();
+    assert_eq!(param_cell(&imported, 20).repr_hash(), legacy.repr_hash());
+    assert_eq!(params_hash(&imported), params_hash(&config));
+
+    // edited value is serialized from typed fields
+    json["params"]["20"]["value"]["gas_price"] = Value::from(1_000_000_000u64);
+    let imported = ConfigParams::from_json(&json).unwrap();
+    assert_ne!(param_cell(&imported, 20).repr_hash(), legacy.repr_hash());
+    assert_eq!(imported.gas_prices(true).unwrap().gas_price, 1_000_000_000);
+    assert_eq!(imported.gas_prices(true).unwrap().gas_limit, 1_000_000);
+}
@@ -0,0 +1,115 @@
+/*
+* Copyright 2018-2020 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use super::*;
+use crate::accounts::StorageExtra;
+
+// cell_price is in nanograms per cell per second, stored shifted by 16 bits
+fn storage_prices(prices: &[(u32, u64)]) -> ConfigParam18 {
+    let mut param = ConfigParam18::default();
+    for (utime_since, cell_price) in prices {
+        let price = StoragePrices {
+            utime_since: *utime_since,
+            bit_price_ps: 0,
+            cell_price_ps: cell_price << 16,
+            mc_bit_price_ps: 0,
+            mc_cell_price_ps: (cell_price * 10) << 16,
+        };
+        param.insert(&price).unwrap();
+    }
+    param
+}
+
+fn one_cell() -> StorageUsed {
+    StorageUsed::with_values_checked(1, 0, StorageExtra::None).unwrap()
+}
+
+#[test]
+fn test_storage_fee_with_future_price() {
+    let param = storage_prices(&[(0, 1), (1200, 2), (3000, 100)]);
+    // 200 seconds by the first price and 300 seconds by the second one,
+    // the price since 3000 is not in effect yet
+    assert_eq!(param.calc_storage_fee(&one_cell(), 1000, 1500, false).unwrap(), 200 + 300 * 2);
+    assert_eq!(param.calc_storage_fee(&one_cell(), 1000, 1500, true).unwrap(), (200 + 300 * 2) * 10);
+    // all three prices
+    assert_eq!(param.calc_storage_fee(&one_cell(), 1000, 3100, false).unwrap(), 200 + 1800 * 2 + 100 * 100);
+}
+
+#[test]
+fn test_storage_fee_before_first_price() {
+    let param = storage_prices(&[(100, 1)]);
+    // period before the first price is free
+    assert_eq!(param.calc_storage_fee(&one_cell(), 50, 200, false).unwrap(), 100);
+    assert_eq!(param.calc_storage_fee(&one_cell(), 50, 100, false).unwrap(), 0);
+    // nothing to pay for
+    assert_eq!(param.calc_storage_fee(&one_cell(), 200, 200, false).unwrap(), 0);
+    assert_eq!(param.calc_storage_fee(&one_cell(), 0, 200, false).unwrap(), 0);
+}
+
+#[test]
+fn test_size_limits_unknown_tag_kept_raw() {
+    let mut builder = BuilderData::new();
+    builder.append_u8(0x03).unwrap();
+    builder.append_u32(12345).unwrap();
+    let cell = builder.into_cell().unwrap();
+    match ConfigParamEnum::construct_from_cell_and_number(cell.clone(), 43).unwrap() {
+        ConfigParamEnum::ConfigParamAny(43, slice) => assert_eq!(slice.into_cell(), cell),
+        param => panic!("unexpected param {:?}", param)
+    }
+
+    let limits = SizeLimitsConfig { max_msg_bits: 1 << 21, ..Default::default() };
+    match ConfigParamEnum::construct_from_cell_and_number(limits.serialize().unwrap(), 43).unwrap() {
+        ConfigParamEnum::ConfigParam43(param) => assert_eq!(param, limits),
+        param => panic!("unexpected param {:?}", param)
+    }
+}
+
+#[test]
+fn test_size_limits_empty_kept_raw() {
+    let cell = BuilderData::new().into_cell().unwrap();
+    match ConfigParamEnum::construct_from_cell_and_number(cell.clone(), 43).unwrap() {
+        ConfigParamEnum::ConfigParamAny(43, slice) => assert_eq!(slice.into_cell(), cell),
+        param => panic!("unexpected param {:?}", param)
+    }
+}
+
+#[test]
+fn test_reserved_params() {
+    let mut builder = BuilderData::new();
+    builder.append_u32(0xDEADBEEF).unwrap();
+    builder.append_u16(0x1234).unwrap();
+    builder.checked_append_reference(Cell::default()).unwrap();
+    let cell = builder.into_cell().unwrap();
+
+    // parameter starts from the current position of the slice and consumes the rest of it
+    let mut slice = SliceData::load_cell(cell).unwrap();
+    slice.get_next_u32().unwrap();
+    let param = ConfigParam26::construct_from(&mut slice).unwrap();
+    assert_eq!(slice.remaining_bits(), 0);
+    assert_eq!(slice.remaining_references(), 0);
+    let mut expected = BuilderData::new();
+    expected.append_u16(0x1234).unwrap();
+    expected.checked_append_reference(Cell::default()).unwrap();
+    let expected = expected.into_cell().unwrap();
+    assert_eq!(param.cell, expected);
+    assert_eq!(param.serialize().unwrap(), expected);
+
+    let mut config = ConfigParams::new();
+    assert_eq!(config.reserved_param(26).unwrap(), None);
+    config.set_config(ConfigParamEnum::ConfigParam26(param)).unwrap();
+    config.set_config(ConfigParamEnum::ConfigParam41(ConfigParam41::with_cell(Cell::default()))).unwrap();
+    assert_eq!(config.reserved_param(26).unwrap(), Some(expected));
+    assert_eq!(config.reserved_param(41).unwrap(), Some(Cell::default()));
+    assert_eq!(config.reserved_param(27).unwrap(), None);
+    config.reserved_param(28).expect_err("param 28 is not reserved");
+}
@@ -0,0 +1,86 @@
+/*
+* Copyright 2018-2020 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use super::*;
+use crate::{
+    accounts::{StorageExtra, StorageUsed},
+    config_params::StoragePrices,
+};
+
+// 1000 nanograms per gas unit, the first 100 gas units are paid by flat price
+fn flat_gas_prices() -> GasLimitsPrices {
+    GasLimitsPrices {
+        gas_price: 1000 << 16,
+        flat_gas_limit: 100,
+        flat_gas_price: 200_000,
+        ..GasLimitsPrices::default()
+    }
+}
+
+fn fee_calculator(fee_in_gas_units: bool) -> FeeCalculator {
+    let mut storage_prices = ConfigParam18::default();
+    storage_prices.insert(&StoragePrices {
+        utime_since: 1,
+        bit_price_ps: 0,
+        cell_price_ps: 1 << 16,
+        mc_bit_price_ps: 0,
+        mc_cell_price_ps: 1 << 16,
+    }).unwrap();
+    FeeCalculator {
+        storage_prices,
+        mc_gas_prices: flat_gas_prices(),
+        gas_prices: flat_gas_prices(),
+        mc_fwd_prices: MsgForwardPrices { lump_price: 400, ..MsgForwardPrices::default() },
+        fwd_prices: MsgForwardPrices { lump_price: 50, ..MsgForwardPrices::default() },
+        fee_in_gas_units,
+        ..FeeCalculator::default()
+    }
+}
+
+fn storage_info(last_paid: u32) -> StorageInfo {
+    let mut info = StorageInfo::with_values(last_paid, None);
+    info.used = StorageUsed::with_values_checked(1, 0, StorageExtra::None).unwrap();
+    info
+}
+
+#[test]
+fn test_fwd_fee_in_gas_units_with_flat_price() {
+    let calc = fee_calculator(true);
+    let msg_cell = Cell::default();
+
+    // 50 gas units are inside of flat limit
+    let fees = calc.msg_fees_for_cell(&msg_cell, false).unwrap();
+    assert_eq!(fees.fwd_fee, Grams::from(200_000u64));
+
+    // 400 gas units: flat price for 100 and 1000 for each of the rest
+    let fees = calc.msg_fees_for_cell(&msg_cell, true).unwrap();
+    assert_eq!(fees.fwd_fee, Grams::from(500_000u64));
+    assert_eq!(fees.fwd_fee.as_u128(), flat_gas_prices().calc_gas_fee(400));
+
+    // without capability fees are in nanograms
+    let fees = fee_calculator(false).msg_fees_for_cell(&msg_cell, false).unwrap();
+    assert_eq!(fees.fwd_fee, Grams::from(50u64));
+}
+
+#[test]
+fn test_storage_fee_in_gas_units_with_flat_price() {
+    let calc = fee_calculator(true);
+    // one cell for 50 and 400 seconds
+    let fee = calc.storage_fee_for_info(&storage_info(1000), false, 1050).unwrap();
+    assert_eq!(fee, Grams::from(200_000u64));
+    let fee = calc.storage_fee_for_info(&storage_info(1000), true, 1400).unwrap();
+    assert_eq!(fee, Grams::from(500_000u64));
+
+    let fee = fee_calculator(false).storage_fee_for_info(&storage_info(1000), false, 1400).unwrap();
+    assert_eq!(fee, Grams::from(400u64));
+}
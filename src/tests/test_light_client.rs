@@ -0,0 +1,212 @@
+/*
+* Copyright 2018-2020 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use super::*;
+use crate::{
+    blocks::{BlockExtra, ValueFlow},
+    config_params::{ConfigParam34, ConfigParam36, ConfigParamEnum},
+    master::McBlockExtra,
+    merkle_update::MerkleUpdate,
+    shard::ShardIdent,
+    signature::{BlockSignatures, BlockSignaturesPure, CryptoSignature, CryptoSignaturePair, SigPubKey},
+    validators::{ValidatorBaseInfo, ValidatorDescr},
+    Serializable,
+};
+use ed25519::signature::Signer;
+use ed25519_dalek::{Keypair, PublicKey, SecretKey};
+use ton_types::UInt256;
+
+const GEN_UTIME: u32 = 1_600_000_000;
+const CC_SEQNO: u32 = 7;
+
+// four validators of equal weight, keys are derived from the seed
+fn keypairs(seed: u8) -> Vec<Keypair> {
+    (0..4).map(|i| {
+        let secret = SecretKey::from_bytes(&[seed + i; 32]).unwrap();
+        let public = PublicKey::from(&secret);
+        Keypair { secret, public }
+    }).collect()
+}
+
+fn validator(keypair: &Keypair) -> ValidatorDescr {
+    ValidatorDescr::with_params(SigPubKey::from_public_key(&keypair.public), 10, None)
+}
+
+fn validator_set(keypairs: &[Keypair]) -> ValidatorSet {
+    let list = keypairs.iter().map(validator).collect();
+    ValidatorSet::new(0, u32::MAX, keypairs.len() as u16, list).unwrap()
+}
+
+fn config(keypairs: &[Keypair]) -> ConfigParams {
+    let mut config = ConfigParams::new();
+    config.set_config(ConfigParamEnum::ConfigParam28(CatchainConfig::default())).unwrap();
+    config.set_config(ConfigParamEnum::ConfigParam34(ConfigParam34 { cur_validators: validator_set(keypairs) })).unwrap();
+    config
+}
+
+// masterchain block signed by `signers` on behalf of `validators`,
+// key block if it has config; state update is pruned as in real proofs
+fn block_proof(
+    seq_no: u32,
+    prev_key_block_seqno: u32,
+    validators: &[Keypair],
+    signers: &[&Keypair],
+    key_block_config: Option<ConfigParams>,
+) -> BlockProof {
+    let (_, hash_short) = validator_set(validators).calc_subset(
+        &CatchainConfig::default(),
+        SHARD_FULL,
+        MASTERCHAIN_ID,
+        CC_SEQNO,
+        GEN_UTIME.into()
+    ).unwrap();
+
+    let mut info = BlockInfo::new();
+    info.set_shard(ShardIdent::masterchain());
+    info.set_seq_no(seq_no).unwrap();
+    info.set_prev_key_block_seqno(prev_key_block_seqno);
+    info.set_gen_utime(GEN_UTIME.into());
+    info.set_gen_catchain_seqno(CC_SEQNO);
+    info.set_gen_validator_list_hash_short(hash_short);
+    let mut extra = BlockExtra::new();
+    if let Some(config) = key_block_config {
+        info.set_key_block(true);
+        let mut custom = McBlockExtra::default();
+        custom.set_config(config);
+        extra.write_custom(Some(&custom)).unwrap();
+    }
+    let block = Block::with_params(-1, info, ValueFlow::default(), MerkleUpdate::default(), extra).unwrap();
+    let root = block.serialize().unwrap();
+    let state_update_hash = block.state_update.cell().repr_hash();
+    let proof = MerkleProof::create(&root, |hash| hash != &state_update_hash).unwrap();
+
+    let block_id = BlockIdExt::with_params(ShardIdent::masterchain(), seq_no, root.repr_hash(), UInt256::rand());
+    let data = Block::build_data_for_sign(&block_id.root_hash, &block_id.file_hash);
+    let mut pure_signatures = BlockSignaturesPure::default();
+    for keypair in signers {
+        let sign = CryptoSignature::from_bytes(&keypair.sign(&data).to_bytes()).unwrap();
+        pure_signatures.add_sigpair(
+            CryptoSignaturePair::with_params(validator(keypair).compute_node_id_short(), sign)
+        );
+    }
+    let signatures = BlockSignatures::with_params(
+        ValidatorBaseInfo::with_params(hash_short, CC_SEQNO),
+        pure_signatures
+    );
+    BlockProof::with_params(block_id, proof.serialize().unwrap(), Some(signatures))
+}
+
+fn key_block_id(seq_no: u32) -> BlockIdExt {
+    BlockIdExt::with_params(ShardIdent::masterchain(), seq_no, UInt256::rand(), UInt256::rand())
+}
+
+#[test]
+fn test_check_mc_block() {
+    let keys = keypairs(1);
+    let client = LightClient::with_config(key_block_id(10), &config(&keys)).unwrap();
+
+    let proof = block_proof(12, 10, &keys, &[&keys[0], &keys[1], &keys[2]], None);
+    let info = client.check_mc_block(&proof).unwrap();
+    assert_eq!(info.seq_no(), 12);
+
+    // proof for another block
+    let mut wrong_proof = proof.clone();
+    wrong_proof.proof_for.root_hash = UInt256::rand();
+    client.check_mc_block(&wrong_proof).expect_err("proof of another block must fail");
+
+    // block after another key block
+    let proof = block_proof(12, 9, &keys, &[&keys[0], &keys[1], &keys[2]], None);
+    client.check_mc_block(&proof).expect_err("wrong previous key block must fail");
+}
+
+#[test]
+fn test_check_mc_block_signatures_weight() {
+    let keys = keypairs(1);
+    let client = LightClient::with_config(key_block_id(10), &config(&keys)).unwrap();
+
+    // half of the weight is not enough
+    let proof = block_proof(12, 10, &keys, &[&keys[0], &keys[1]], None);
+    client.check_mc_block(&proof).expect_err("half of weight must fail");
+
+    // repeated signature of the same validator is counted once
+    let proof = block_proof(12, 10, &keys, &[&keys[0], &keys[0], &keys[0], &keys[1]], None);
+    client.check_mc_block(&proof).expect_err("repeated signatures must fail");
+
+    // signatures of unknown validators are not counted
+    let others = keypairs(101);
+    let proof = block_proof(12, 10, &keys, &[&keys[0], &keys[1], &others[0], &others[1]], None);
+    client.check_mc_block(&proof).expect_err("signatures of other validators must fail");
+
+    // signed by another validator set
+    let proof = block_proof(12, 10, &others, &[&others[0], &others[1], &others[2]], None);
+    client.check_mc_block(&proof).expect_err("another validator set must fail");
+}
+
+#[test]
+fn test_apply_key_blocks() {
+    let keys_a = keypairs(1);
+    let keys_b = keypairs(11);
+    let keys_c = keypairs(21);
+    let mut client = LightClient::with_config(key_block_id(10), &config(&keys_a)).unwrap();
+
+    let proofs = [
+        block_proof(20, 10, &keys_a, &[&keys_a[1], &keys_a[2], &keys_a[3]], Some(config(&keys_b))),
+        block_proof(30, 20, &keys_b, &[&keys_b[0], &keys_b[1], &keys_b[3]], Some(config(&keys_c))),
+    ];
+    client.apply_key_blocks(&proofs).unwrap();
+    assert_eq!(client.key_block(), &proofs[1].proof_for);
+    assert_eq!(client.validators(), &validator_set(&keys_c));
+
+    let proof = block_proof(31, 30, &keys_c, &[&keys_c[0], &keys_c[1], &keys_c[2]], None);
+    client.check_mc_block(&proof).unwrap();
+    let proof = block_proof(31, 30, &keys_a, &[&keys_a[0], &keys_a[1], &keys_a[2]], None);
+    client.check_mc_block(&proof).expect_err("validators of old key block must fail");
+
+    // key block out of the chain
+    let proof = block_proof(40, 20, &keys_c, &[&keys_c[0], &keys_c[1], &keys_c[2]], Some(config(&keys_a)));
+    client.apply_key_block(&proof).expect_err("skipped key block must fail");
+    assert_eq!(client.key_block(), &proofs[1].proof_for);
+
+    // ordinary block doesn't move the client
+    let proof = block_proof(31, 30, &keys_c, &[&keys_c[0], &keys_c[1], &keys_c[2]], None);
+    client.apply_key_block(&proof).expect_err("not a key block must fail");
+    assert_eq!(client.key_block(), &proofs[1].proof_for);
+}
+
+#[test]
+fn test_next_validators_are_not_trusted() {
+    let keys_a = keypairs(1);
+    let keys_b = keypairs(11);
+    let mut config_a = config(&keys_a);
+    config_a.set_config(ConfigParamEnum::ConfigParam36(ConfigParam36 { next_validators: validator_set(&keys_b) })).unwrap();
+    let client = LightClient::with_config(key_block_id(10), &config_a).unwrap();
+
+    let proof = block_proof(12, 10, &keys_b, &[&keys_b[0], &keys_b[1], &keys_b[2]], None);
+    client.check_mc_block(&proof).expect_err("next validators must fail");
+    let proof = block_proof(12, 10, &keys_a, &[&keys_a[0], &keys_a[1], &keys_a[2]], None);
+    client.check_mc_block(&proof).unwrap();
+}
+
+#[test]
+fn test_with_key_block_proof() {
+    let keys_a = keypairs(1);
+    let keys_b = keypairs(11);
+    let proof = block_proof(20, 10, &keys_a, &[], Some(config(&keys_b)));
+    let client = LightClient::with_key_block_proof(&proof).unwrap();
+    assert_eq!(client.key_block(), &proof.proof_for);
+    assert_eq!(client.validators(), &validator_set(&keys_b));
+
+    let proof = block_proof(21, 20, &keys_b, &[&keys_b[0], &keys_b[1], &keys_b[2]], None);
+    client.check_mc_block(&proof).unwrap();
+    LightClient::with_key_block_proof(&proof).expect_err("not a key block must fail");
+}
@@ -0,0 +1,183 @@
+/*
+* Copyright 2018-2020 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use super::*;
+use crate::config_params::{ConfigParam18, ConfigParamEnum, ConfigParams, GasLimitsPrices, StoragePrices};
+
+const NOW: u32 = 1_600_000_000;
+const LT: u64 = 1000;
+const VALUE: u64 = 1_000_000_000;
+
+fn address(byte: u8, workchain_id: i8) -> MsgAddressInt {
+    MsgAddressInt::with_standart(None, workchain_id, AccountId::from([byte; 32])).unwrap()
+}
+
+// 320 bits, longer than the part kept in bounced body
+fn body() -> SliceData {
+    let mut builder = BuilderData::new();
+    for i in 0..10 {
+        builder.append_u32(0x0101_0101 * i).unwrap();
+    }
+    SliceData::load_builder(builder).unwrap()
+}
+
+fn bounceable_message(dst_workchain_id: i8) -> Message {
+    let mut header = InternalMessageHeader::with_addresses(
+        address(1, 0),
+        address(2, dst_workchain_id),
+        CurrencyCollection::with_grams(VALUE)
+    );
+    header.bounce = true;
+    Message::with_int_header_and_body(header, body())
+}
+
+fn caps(list: &[GlobalCapabilities]) -> CapabilitiesSet {
+    let mut caps = CapabilitiesSet::new();
+    for cap in list {
+        caps.insert(*cap);
+    }
+    caps
+}
+
+fn fwd_prices(lump_price: u64) -> MsgForwardPrices {
+    MsgForwardPrices {
+        lump_price,
+        bit_price: 1 << 16,
+        cell_price: 100 << 16,
+        first_frac: 21845,
+        ..MsgForwardPrices::default()
+    }
+}
+
+fn fee_calculator() -> FeeCalculator {
+    let mut storage_prices = ConfigParam18::default();
+    storage_prices.insert(&StoragePrices::default()).unwrap();
+    let mut config = ConfigParams::new();
+    config.set_config(ConfigParamEnum::ConfigParam18(storage_prices)).unwrap();
+    config.set_config(ConfigParamEnum::ConfigParam20(GasLimitsPrices::default())).unwrap();
+    config.set_config(ConfigParamEnum::ConfigParam21(GasLimitsPrices::default())).unwrap();
+    config.set_config(ConfigParamEnum::ConfigParam24(fwd_prices(10_000))).unwrap();
+    config.set_config(ConfigParamEnum::ConfigParam25(fwd_prices(1000))).unwrap();
+    FeeCalculator::with_config(&config).unwrap()
+}
+
+#[test]
+fn test_make_bounced_header() {
+    let msg = bounceable_message(0);
+    let bounced = msg.make_bounced(&CapabilitiesSet::new(), &MsgForwardPrices::default(), NOW, LT)
+        .unwrap().unwrap();
+    let header = bounced.int_header().unwrap();
+    assert!(header.bounced);
+    assert!(!header.bounce);
+    assert_eq!(bounced.src_ref(), msg.dst_ref());
+    assert_eq!(bounced.dst_ref(), msg.src_ref());
+    assert_eq!(header.created_lt, LT);
+    assert_eq!(header.created_at, UnixTime32::new(NOW));
+    assert_eq!(header.value, CurrencyCollection::with_grams(VALUE));
+}
+
+#[test]
+fn test_make_bounced_body() {
+    let msg = bounceable_message(0);
+    let prices = MsgForwardPrices::default();
+    let original = msg.body().unwrap();
+
+    // without CapBounceMsgBody body is dropped, CapFullBodyInBounced alone changes nothing
+    for caps in [caps(&[]), caps(&[GlobalCapabilities::CapFullBodyInBounced])] {
+        let bounced = msg.make_bounced(&caps, &prices, NOW, LT).unwrap().unwrap();
+        assert!(bounced.body().is_none());
+    }
+
+    // 0xffffffff and first 256 bits of the body
+    let caps_body = caps(&[GlobalCapabilities::CapBounceMsgBody]);
+    let bounced = msg.make_bounced(&caps_body, &prices, NOW, LT).unwrap().unwrap();
+    let mut body = bounced.body().unwrap();
+    assert_eq!(body.remaining_bits(), 32 + 256);
+    assert_eq!(body.remaining_references(), 0);
+    assert_eq!(body.get_next_u32().unwrap(), 0xFFFF_FFFF);
+    assert_eq!(body.get_next_bits(256).unwrap(), original.clone().get_next_bits(256).unwrap());
+
+    // body shorter than 256 bits is kept as is
+    let mut short = msg.clone();
+    short.set_body(SliceData::new(vec![0x12, 0x34, 0x80]));
+    let bounced = short.make_bounced(&caps_body, &prices, NOW, LT).unwrap().unwrap();
+    let mut body = bounced.body().unwrap();
+    assert_eq!(body.remaining_bits(), 32 + 16);
+    assert_eq!(body.get_next_u32().unwrap(), 0xFFFF_FFFF);
+    assert_eq!(body.get_next_bits(16).unwrap(), vec![0x12, 0x34]);
+
+    // whole original body is added as reference
+    let caps_full = caps(&[GlobalCapabilities::CapBounceMsgBody, GlobalCapabilities::CapFullBodyInBounced]);
+    let bounced = msg.make_bounced(&caps_full, &prices, NOW, LT).unwrap().unwrap();
+    let mut body = bounced.body().unwrap();
+    assert_eq!(body.remaining_bits(), 32 + 256);
+    assert_eq!(body.remaining_references(), 1);
+    assert_eq!(body.reference(0).unwrap().repr_hash(), original.clone().into_cell().repr_hash());
+    assert_eq!(body.get_next_u32().unwrap(), 0xFFFF_FFFF);
+    assert_eq!(body.get_next_bits(256).unwrap(), original.clone().get_next_bits(256).unwrap());
+}
+
+#[test]
+fn test_make_bounced_fees() {
+    let msg = bounceable_message(0);
+    // message without body is one cell, so forward fee is lump price only
+    let bounced = msg.make_bounced(&CapabilitiesSet::new(), &fwd_prices(1000), NOW, LT).unwrap().unwrap();
+    let header = bounced.int_header().unwrap();
+    assert_eq!(header.value.grams, Grams::from(VALUE - 1000));
+    assert_eq!(header.fwd_fee, Grams::from(1000 - 333u64));
+
+    // value doesn't cover forward fee
+    let mut poor = msg.clone();
+    poor.int_header_mut().unwrap().value = CurrencyCollection::with_grams(999);
+    assert!(poor.make_bounced(&CapabilitiesSet::new(), &fwd_prices(1000), NOW, LT).unwrap().is_none());
+    poor.int_header_mut().unwrap().value = CurrencyCollection::with_grams(1000);
+    let bounced = poor.make_bounced(&CapabilitiesSet::new(), &fwd_prices(1000), NOW, LT).unwrap().unwrap();
+    assert!(bounced.int_header().unwrap().value.grams.is_zero());
+
+    // fee calculator uses prices of the bouncing account's workchain and given value
+    let fees = fee_calculator();
+    let value = CurrencyCollection::with_grams(5000);
+    let bounced = msg.make_bounced_with_fees(&CapabilitiesSet::new(), &fees, value.clone(), NOW, LT)
+        .unwrap().unwrap();
+    assert_eq!(bounced.int_header().unwrap().value.grams, Grams::from(4000u64));
+    let mc_msg = bounceable_message(-1);
+    assert!(mc_msg.make_bounced_with_fees(&CapabilitiesSet::new(), &fees, value, NOW, LT).unwrap().is_none());
+    let bounced = mc_msg.make_bounced_with_fees(
+        &CapabilitiesSet::new(), &fees, CurrencyCollection::with_grams(15000), NOW, LT
+    ).unwrap().unwrap();
+    assert_eq!(bounced.int_header().unwrap().value.grams, Grams::from(5000u64));
+}
+
+#[test]
+fn test_make_bounced_none() {
+    let caps = caps(&[GlobalCapabilities::CapBounceMsgBody]);
+    let prices = MsgForwardPrices::default();
+
+    let mut msg = bounceable_message(0);
+    msg.int_header_mut().unwrap().bounce = false;
+    assert!(msg.make_bounced(&caps, &prices, NOW, LT).unwrap().is_none());
+
+    // bounced message is never bounced again
+    let mut msg = bounceable_message(0);
+    msg.int_header_mut().unwrap().bounced = true;
+    assert!(msg.make_bounced(&caps, &prices, NOW, LT).unwrap().is_none());
+
+    let mut msg = bounceable_message(0);
+    msg.int_header_mut().unwrap().src = MsgAddressIntOrNone::None;
+    assert!(msg.make_bounced(&caps, &prices, NOW, LT).unwrap().is_none());
+
+    let msg = Message::with_ext_in_header(ExternalInboundMessageHeader::new(MsgAddressExt::AddrNone, address(2, 0)));
+    assert!(msg.make_bounced(&caps, &prices, NOW, LT).unwrap().is_none());
+    let value = CurrencyCollection::with_grams(VALUE);
+    assert!(msg.make_bounced_with_fees(&caps, &FeeCalculator::default(), value, NOW, LT).unwrap().is_none());
+}
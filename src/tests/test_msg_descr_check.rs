@@ -0,0 +1,254 @@
+/*
+* Copyright 2018-2020 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use super::*;
+use crate::{
+    accounts::AccountStatus,
+    blocks::{BlockExtra, BlockInfo, ValueFlow},
+    merkle_update::MerkleUpdate,
+    messages::{InternalMessageHeader, Message, MsgAddressInt},
+    transactions::ShardAccountBlocks,
+    types::{CurrencyCollection, Grams},
+};
+use ton_types::AccountId;
+
+const LT: u64 = 100;
+
+fn message(dst: u8) -> Message {
+    let src = MsgAddressInt::with_standart(None, 0, AccountId::from([1; 32])).unwrap();
+    let dst = MsgAddressInt::with_standart(None, 0, AccountId::from([dst; 32])).unwrap();
+    Message::with_int_header(InternalMessageHeader::with_addresses(src, dst, CurrencyCollection::with_grams(1_000_000)))
+}
+
+fn hash(msg: &Message) -> UInt256 {
+    msg.serialize().unwrap().repr_hash()
+}
+
+fn envelope(msg: &Message, fwd_fee: u64) -> MsgEnvelope {
+    MsgEnvelope::with_message_and_fee(msg, Grams::from(fwd_fee)).unwrap()
+}
+
+fn env_cell(msg: &Message, fwd_fee: u64) -> Cell {
+    envelope(msg, fwd_fee).serialize().unwrap()
+}
+
+// transit message taking part of forward fee
+fn transit(msg: &Message) -> InMsg {
+    InMsg::transit(env_cell(msg, 2000), env_cell(msg, 1000), Grams::from(1000u64))
+}
+
+fn final_msg(msg: &Message, tr: &Transaction) -> InMsg {
+    InMsg::final_msg(env_cell(msg, 1000), tr.serialize().unwrap(), Grams::from(1000u64))
+}
+
+fn transaction(lt: u64, in_msg: Option<&Message>, out_msgs: &[&Message]) -> Transaction {
+    let mut tr = Transaction::with_address_and_status(AccountId::from([2; 32]), AccountStatus::AccStateActive);
+    tr.set_logical_time(lt);
+    tr.write_in_msg(in_msg).unwrap();
+    for msg in out_msgs {
+        tr.add_out_message(msg).unwrap();
+    }
+    tr
+}
+
+fn enqueue(queue: &mut OutMsgQueue, env: &MsgEnvelope) {
+    let (_, next_prefix) = env.calc_cur_next_prefix().unwrap();
+    queue.insert(next_prefix.workchain_id, next_prefix.prefix, env, LT).unwrap();
+}
+
+// out messages are given with their keys to be able to break them
+fn check(
+    in_msgs: &[InMsg],
+    out_msgs: &[(UInt256, OutMsg)],
+    transactions: &[&Transaction],
+    prev_queue: Option<&OutMsgQueue>
+) -> Vec<BrokenMsgLink> {
+    let mut in_msg_descr = InMsgDescr::default();
+    for in_msg in in_msgs {
+        in_msg_descr.insert(in_msg).unwrap();
+    }
+    let mut out_msg_descr = OutMsgDescr::default();
+    for (key, out_msg) in out_msgs {
+        out_msg_descr.insert_with_key(key.clone(), out_msg).unwrap();
+    }
+    let mut account_blocks = ShardAccountBlocks::default();
+    for tr in transactions {
+        account_blocks.add_transaction(tr).unwrap();
+    }
+    let mut extra = BlockExtra::new();
+    extra.write_in_msg_descr(&in_msg_descr).unwrap();
+    extra.write_out_msg_descr(&out_msg_descr).unwrap();
+    extra.write_account_blocks(&account_blocks).unwrap();
+    let block = Block::with_params(0, BlockInfo::new(), ValueFlow::default(), MerkleUpdate::default(), extra).unwrap();
+    block.check_msg_descrs(prev_queue).unwrap()
+}
+
+fn errors(broken: &[BrokenMsgLink]) -> Vec<MsgLinkError> {
+    broken.iter().map(|link| link.error.clone()).collect()
+}
+
+#[test]
+fn test_consistent_block() {
+    let mut queue = OutMsgQueue::default();
+
+    // final import and new export of one transaction
+    let (imported, exported) = (message(3), message(4));
+    let tr1 = transaction(LT + 1, Some(&imported), &[&exported]);
+    let in_final = final_msg(&imported, &tr1);
+    let new_msg = OutMsg::new(env_cell(&exported, 1000), tr1.serialize().unwrap());
+
+    // message processed immediately by another transaction
+    let immediate = message(5);
+    let tr2 = transaction(LT + 2, Some(&immediate), &[]);
+    let tr3 = transaction(LT + 3, None, &[&immediate]);
+    let immediate_env = env_cell(&immediate, 0);
+    let in_immediate = InMsg::immediate(immediate_env.clone(), tr2.serialize().unwrap(), Grams::default());
+    let out_immediate = OutMsg::immediate(immediate_env, tr3.serialize().unwrap(), in_immediate.serialize().unwrap());
+
+    // transit message
+    let forwarded = message(6);
+    let in_transit = transit(&forwarded);
+    let out_transit = OutMsg::transit(env_cell(&forwarded, 1000), in_transit.serialize().unwrap(), false);
+
+    // messages dequeued from the queue of the previous state
+    let dequeued = message(7);
+    enqueue(&mut queue, &envelope(&dequeued, 1000));
+    let dequeue = OutMsg::dequeue_long(env_cell(&dequeued, 1000), LT);
+    let dequeued_short = message(8);
+    let short_env = envelope(&dequeued_short, 1000);
+    enqueue(&mut queue, &short_env);
+    let (_, next_prefix) = short_env.calc_cur_next_prefix().unwrap();
+    let dequeue_short = OutMsg::dequeue_short(short_env.serialize().unwrap().repr_hash(), &next_prefix, LT);
+
+    // transit message taken from the queue and enqueued again
+    let requeued = message(9);
+    enqueue(&mut queue, &envelope(&requeued, 2000));
+    let in_requeued = transit(&requeued);
+    let out_requeued = OutMsg::transit(env_cell(&requeued, 1000), in_requeued.serialize().unwrap(), true);
+
+    let broken = check(
+        &[in_final, in_immediate, in_transit, in_requeued],
+        &[
+            (hash(&exported), new_msg),
+            (hash(&immediate), out_immediate),
+            (hash(&forwarded), out_transit),
+            (hash(&dequeued), dequeue),
+            (hash(&dequeued_short), dequeue_short),
+            (hash(&requeued), out_requeued),
+        ],
+        &[&tr1, &tr2, &tr3],
+        Some(&queue)
+    );
+    assert!(broken.is_empty());
+}
+
+#[test]
+fn test_wrong_key() {
+    let msg = message(3);
+    let dequeue = OutMsg::dequeue_long(env_cell(&msg, 1000), LT);
+    let broken = check(&[], &[(hash(&message(4)), dequeue)], &[], None);
+    assert_eq!(errors(&broken), vec![MsgLinkError::WrongKey(hash(&msg))]);
+    assert_eq!(broken[0].msg_hash, hash(&message(4)));
+}
+
+#[test]
+fn test_transaction_not_found() {
+    let msg = message(3);
+    let tr = transaction(LT, Some(&msg), &[]);
+    let in_msg = final_msg(&msg, &tr);
+    let broken = check(&[in_msg], &[], &[], None);
+    assert_eq!(errors(&broken), vec![MsgLinkError::TransactionNotFound]);
+    assert_eq!(broken[0].transaction_lt, None);
+}
+
+#[test]
+fn test_wrong_in_msg() {
+    let msg = message(3);
+    let tr = transaction(LT, None, &[]);
+    let in_msg = final_msg(&msg, &tr);
+    let broken = check(&[in_msg], &[], &[&tr], None);
+    assert_eq!(errors(&broken), vec![MsgLinkError::WrongInMsg]);
+    assert_eq!(broken[0].transaction_lt, Some(LT));
+}
+
+#[test]
+fn test_wrong_out_msg() {
+    let msg = message(3);
+    let tr = transaction(LT, None, &[]);
+    let out_msg = OutMsg::new(env_cell(&msg, 1000), tr.serialize().unwrap());
+    let broken = check(&[], &[(hash(&msg), out_msg)], &[&tr], None);
+    assert_eq!(errors(&broken), vec![MsgLinkError::WrongOutMsg]);
+    assert_eq!(broken[0].transaction_lt, Some(LT));
+}
+
+#[test]
+fn test_no_msg_descr() {
+    let msg = message(3);
+    let tr = transaction(LT, Some(&msg), &[]);
+    let broken = check(&[], &[], &[&tr], None);
+    assert_eq!(errors(&broken), vec![MsgLinkError::NoInMsgDescr]);
+    assert_eq!((&broken[0].msg_hash, broken[0].transaction_lt), (&hash(&msg), Some(LT)));
+
+    let tr = transaction(LT, None, &[&msg]);
+    let broken = check(&[], &[], &[&tr], None);
+    assert_eq!(errors(&broken), vec![MsgLinkError::NoOutMsgDescr]);
+    assert_eq!((&broken[0].msg_hash, broken[0].transaction_lt), (&hash(&msg), Some(LT)));
+}
+
+#[test]
+fn test_not_paired() {
+    let msg = message(3);
+    let in_transit = transit(&msg);
+    let broken = check(&[in_transit], &[], &[], None);
+    assert_eq!(errors(&broken), vec![MsgLinkError::TransitNotPaired]);
+
+    let tr = transaction(LT, Some(&msg), &[]);
+    let in_immediate = InMsg::immediate(env_cell(&msg, 0), tr.serialize().unwrap(), Grams::default());
+    let broken = check(&[in_immediate], &[], &[&tr], None);
+    assert_eq!(errors(&broken), vec![MsgLinkError::ReimportNotPaired]);
+}
+
+#[test]
+fn test_dequeue() {
+    let msg = message(3);
+    let dequeue = OutMsg::dequeue_long(env_cell(&msg, 1000), LT);
+
+    // queue is not checked without previous state
+    assert!(check(&[], &[(hash(&msg), dequeue.clone())], &[], None).is_empty());
+
+    let broken = check(&[], &[(hash(&msg), dequeue.clone())], &[], Some(&OutMsgQueue::default()));
+    assert_eq!(errors(&broken), vec![MsgLinkError::NotInQueue]);
+
+    let mut queue = OutMsgQueue::default();
+    enqueue(&mut queue, &envelope(&msg, 2000));
+    let broken = check(&[], &[(hash(&msg), dequeue)], &[], Some(&queue));
+    assert_eq!(errors(&broken), vec![MsgLinkError::WrongEnvelope]);
+}
+
+#[test]
+fn test_transit_requeued() {
+    let msg = message(3);
+    let in_msg = transit(&msg);
+    let out_msg = OutMsg::transit(env_cell(&msg, 1000), in_msg.serialize().unwrap(), true);
+    let in_msgs = [in_msg];
+    let out_msgs = [(hash(&msg), out_msg)];
+
+    let broken = check(&in_msgs, &out_msgs, &[], Some(&OutMsgQueue::default()));
+    assert_eq!(errors(&broken), vec![MsgLinkError::NotInQueue]);
+
+    // queue must contain the imported envelope, not the requeued one
+    let mut queue = OutMsgQueue::default();
+    enqueue(&mut queue, &envelope(&msg, 1000));
+    let broken = check(&in_msgs, &out_msgs, &[], Some(&queue));
+    assert_eq!(errors(&broken), vec![MsgLinkError::WrongEnvelope]);
+}
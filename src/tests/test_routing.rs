@@ -0,0 +1,133 @@
+/*
+* Copyright 2018-2020 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use super::*;
+use crate::{
+    bintree::BinTree,
+    master::ShardDescr,
+    messages::{InternalMessageHeader, MsgAddressInt},
+    shard::BASE_WORKCHAIN_ID,
+    types::{CurrencyCollection, InRefValue},
+};
+use ton_types::AccountId;
+
+// shards of basechain: 0, 10, 110, 1110, 11110, 11111
+fn split_shards() -> ShardHashes {
+    let mut shards = ShardHashes::default();
+    let tree = BinTree::with_item(&ShardDescr::default()).unwrap();
+    shards.set(&BASE_WORKCHAIN_ID, &InRefValue(tree)).unwrap();
+    let mut shard = ShardIdent::with_workchain_id(BASE_WORKCHAIN_ID).unwrap();
+    for _ in 0..5 {
+        shards.split_shard(&shard, |descr| Ok((descr.clone(), descr))).unwrap();
+        shard = shard.split().unwrap().1;
+    }
+    shards
+}
+
+fn shard(tagged_prefix: u64) -> ShardIdent {
+    ShardIdent::with_tagged_prefix(BASE_WORKCHAIN_ID, tagged_prefix).unwrap()
+}
+
+fn address(first_byte: u8) -> MsgAddressInt {
+    let mut account_id = [0; 32];
+    account_id[0] = first_byte;
+    MsgAddressInt::with_standart(None, 0, AccountId::from(account_id)).unwrap()
+}
+
+fn message(src: u8, dst: u8) -> Message {
+    let header = InternalMessageHeader::with_addresses(
+        address(src),
+        address(dst),
+        CurrencyCollection::with_grams(1_000_000_000)
+    );
+    Message::with_int_header(header)
+}
+
+#[test]
+fn test_route_over_split_shards() {
+    let shards = split_shards();
+    let router = MsgRouter::new(&shards, false);
+    let src = AccountIdPrefixFull::workchain(BASE_WORKCHAIN_ID, 0);
+    let dest = AccountIdPrefixFull::workchain(BASE_WORKCHAIN_ID, 0xFF00_0000_0000_0000);
+
+    // first hex digit of destination is taken in the source shard
+    let route = router.route(&src, &dest, IntermediateAddress::full_src()).unwrap();
+    assert_eq!(route.cur_shard, shard(0x4000_0000_0000_0000));
+    assert_eq!(route.next_shard, shard(0xF400_0000_0000_0000));
+    assert_eq!(route.cur_addr, IntermediateAddress::use_dest_bits(32).unwrap());
+    assert_eq!(route.next_addr, IntermediateAddress::use_dest_bits(36).unwrap());
+    assert!(!route.is_delivered());
+
+    // then the second one in the transit shard
+    let route = router.route(&src, &dest, route.next_addr).unwrap();
+    assert_eq!(route.cur_shard, shard(0xF400_0000_0000_0000));
+    assert_eq!(route.next_shard, shard(0xFC00_0000_0000_0000));
+    assert_eq!(route.cur_addr, IntermediateAddress::use_dest_bits(36).unwrap());
+    assert_eq!(route.next_addr, IntermediateAddress::use_dest_bits(40).unwrap());
+
+    let route = router.route(&src, &dest, route.next_addr).unwrap();
+    assert_eq!(route.cur_shard, shard(0xFC00_0000_0000_0000));
+    assert_eq!(route.next_addr, IntermediateAddress::full_dest());
+    assert!(route.is_delivered());
+}
+
+#[test]
+fn test_route_off_hypercube() {
+    let shards = split_shards();
+    let router = MsgRouter::new(&shards, true);
+    let src = AccountIdPrefixFull::workchain(BASE_WORKCHAIN_ID, 0);
+    let dest = AccountIdPrefixFull::workchain(BASE_WORKCHAIN_ID, 0xFF00_0000_0000_0000);
+
+    let route = router.route(&src, &dest, IntermediateAddress::full_src()).unwrap();
+    assert_eq!(route.cur_shard, shard(0x4000_0000_0000_0000));
+    assert_eq!(route.next_shard, shard(0xFC00_0000_0000_0000));
+    assert_eq!(route.next_addr, IntermediateAddress::full_dest());
+}
+
+#[test]
+fn test_next_hop_until_delivered() {
+    let shards = split_shards();
+    let router = MsgRouter::new(&shards, false);
+    let msg = message(0x00, 0xFF);
+
+    let (mut env, mut route) = router.make_envelope(&msg, Grams::from(1000u64)).unwrap();
+    assert_eq!(route.cur_shard, shard(0x4000_0000_0000_0000));
+    let mut hops = vec![route.next_shard];
+    while !route.is_delivered() {
+        let (next_env, next_route) = router.next_hop(&env).unwrap();
+        assert_eq!(next_env.cur_addr(), &next_route.cur_addr);
+        assert_eq!(next_env.next_addr(), &next_route.next_addr);
+        assert_eq!(next_env.fwd_fee_remaining(), env.fwd_fee_remaining());
+        env = next_env;
+        route = next_route;
+        hops.push(route.next_shard);
+    }
+    assert_eq!(hops, vec![
+        shard(0xF400_0000_0000_0000),
+        shard(0xFC00_0000_0000_0000),
+        shard(0xFC00_0000_0000_0000),
+    ]);
+    assert_eq!(env.next_addr(), &IntermediateAddress::full_dest());
+}
+
+#[test]
+fn test_route_inside_shard() {
+    let shards = split_shards();
+    let router = MsgRouter::new(&shards, false);
+    let msg = message(0x10, 0x20);
+
+    let route = router.route_message(&msg).unwrap();
+    assert_eq!(route.cur_shard, shard(0x4000_0000_0000_0000));
+    assert_eq!(route.cur_addr, IntermediateAddress::full_dest());
+    assert!(route.is_delivered());
+}
@@ -0,0 +1,131 @@
+/*
+* Copyright 2018-2020 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use super::*;
+use crate::{
+    envelope_message::MsgEnvelope,
+    messages::{InternalMessageHeader, Message},
+    outbound_messages::OutMsgQueue,
+    shard_accounts::DepthBalanceInfo,
+    types::Grams,
+};
+
+// first bytes of account ids in the left and right halves of the basechain
+const LEFT: [u8; 2] = [0x10, 0x30];
+const RIGHT: [u8; 2] = [0x90, 0xF0];
+const REST: u64 = 1000;
+const VALIDATOR_FEES: u64 = 500;
+
+fn address(byte: u8) -> MsgAddressInt {
+    MsgAddressInt::with_standart(None, 0, AccountId::from([byte; 32])).unwrap()
+}
+
+fn balance(byte: u8) -> CurrencyCollection {
+    CurrencyCollection::with_grams(byte as u64 * 1_000_000)
+}
+
+fn total(bytes: &[u8]) -> CurrencyCollection {
+    let mut total = CurrencyCollection::default();
+    for byte in bytes {
+        total.add(&balance(*byte)).unwrap();
+    }
+    total
+}
+
+// message enqueued in the shard of its source
+fn enqueue(queue: &mut OutMsgQueue, src: u8) {
+    let value = CurrencyCollection::with_grams(1000);
+    let header = InternalMessageHeader::with_addresses(address(src), address(0x55), value);
+    let env = MsgEnvelope::with_routing(
+        Message::with_int_header(header).serialize().unwrap(),
+        Grams::from(100u64),
+        IntermediateAddress::full_src(),
+        IntermediateAddress::full_dest()
+    );
+    let (_, next_prefix) = env.calc_cur_next_prefix().unwrap();
+    queue.insert(next_prefix.workchain_id, next_prefix.prefix, &env, 1).unwrap();
+}
+
+// basechain state with accounts and enqueued messages in both halves
+fn state() -> ShardStateUnsplit {
+    let mut state = ShardStateUnsplit::with_ident(ShardIdent::default());
+    let mut queue_info = OutMsgQueueInfo::new();
+    for byte in LEFT.iter().chain(RIGHT.iter()) {
+        let account = Account::with_address_and_ballance(&address(*byte), &balance(*byte));
+        let account = ShardAccount::with_params(&account, UInt256::default(), 0).unwrap();
+        state.insert_account(&UInt256::from([*byte; 32]), &account).unwrap();
+        enqueue(queue_info.out_queue_mut(), *byte);
+    }
+    state.write_out_msg_queue_info(&queue_info).unwrap();
+    let mut total_balance = total(&LEFT);
+    total_balance.add(&total(&RIGHT)).unwrap();
+    total_balance.grams.add(&Grams::from(REST)).unwrap();
+    state.set_total_balance(total_balance);
+    state.set_total_validator_fees(CurrencyCollection::with_grams(VALIDATOR_FEES));
+    state
+}
+
+fn queue_sources(state: &ShardStateUnsplit) -> Vec<MsgAddressInt> {
+    let mut sources = Vec::new();
+    state.read_out_msg_queue_info().unwrap().out_queue().iterate_objects(|enq| {
+        sources.extend(enq.read_out_msg()?.read_message()?.src_ref().cloned());
+        Ok(true)
+    }).unwrap();
+    sources
+}
+
+fn check_child(child: &ShardStateUnsplit, shard: &ShardIdent, bytes: &[u8], total_balance: CurrencyCollection) {
+    assert_eq!(child.shard(), shard);
+    let accounts = child.read_accounts().unwrap();
+    assert_eq!(accounts.root_extra(), &DepthBalanceInfo::new(0, &total(bytes)).unwrap());
+    for byte in LEFT.iter().chain(RIGHT.iter()) {
+        let id = AccountId::from([*byte; 32]);
+        assert_eq!(accounts.account(&id).unwrap().is_some(), bytes.contains(byte));
+    }
+    let sources = queue_sources(child);
+    assert_eq!(sources.len(), bytes.len());
+    for byte in bytes {
+        assert!(sources.contains(&address(*byte)));
+    }
+    assert_eq!(child.total_balance(), &total_balance);
+}
+
+#[test]
+fn test_split_merge() {
+    let state = state();
+    let (left, right) = state.split().unwrap();
+    let (left_shard, right_shard) = state.shard().split().unwrap();
+
+    // rest of the total balance and validator fees stay in the left child
+    let mut left_balance = total(&LEFT);
+    left_balance.grams.add(&Grams::from(REST)).unwrap();
+    check_child(&left, &left_shard, &LEFT, left_balance);
+    check_child(&right, &right_shard, &RIGHT, total(&RIGHT));
+    assert_eq!(left.total_validator_fees(), &CurrencyCollection::with_grams(VALIDATOR_FEES));
+    assert_eq!(right.total_validator_fees(), &CurrencyCollection::default());
+
+    let merged = ShardStateUnsplit::merge(&left, &right).unwrap();
+    assert_eq!(merged.shard(), state.shard());
+    assert_eq!(merged.accounts_cell().repr_hash(), state.accounts_cell().repr_hash());
+    assert_eq!(merged.read_accounts().unwrap().root_extra(), state.read_accounts().unwrap().root_extra());
+    assert_eq!(
+        merged.read_out_msg_queue_info().unwrap().out_queue(),
+        state.read_out_msg_queue_info().unwrap().out_queue()
+    );
+    assert_eq!(merged.total_balance(), state.total_balance());
+    assert_eq!(merged.total_validator_fees(), state.total_validator_fees());
+
+    // siblings only in the right order
+    ShardStateUnsplit::merge(&right, &left).expect_err("right and left must not be merged");
+    ShardStateUnsplit::merge(&left, &left).expect_err("shard must not be merged with itself");
+}
@@ -0,0 +1,85 @@
+/*
+* Copyright 2018-2020 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use super::*;
+use ed25519_dalek::{Keypair, PublicKey, SecretKey};
+
+const GLOBAL_ID: i32 = 42;
+
+fn keypair(seed: u8) -> Keypair {
+    let secret = SecretKey::from_bytes(&[seed; 32]).unwrap();
+    let public = PublicKey::from(&secret);
+    Keypair { secret, public }
+}
+
+fn public_key(keypair: &Keypair) -> SigPubKey {
+    SigPubKey::from_public_key(&keypair.public)
+}
+
+// 64 bits of data and a reference
+fn body() -> SliceData {
+    let mut builder = BuilderData::new();
+    builder.append_u64(0x0123_4567_89AB_CDEF).unwrap();
+    builder.checked_append_reference(Cell::default()).unwrap();
+    SliceData::load_builder(builder).unwrap()
+}
+
+#[test]
+fn test_sign_verify_body() {
+    let signer = keypair(1);
+    for signature_id in [None, Some(GLOBAL_ID)] {
+        let signed = sign_body(&body(), &signer, signature_id).unwrap();
+        assert_eq!(signed.remaining_bits(), 512 + 64);
+        assert_eq!(signed.remaining_references(), 1);
+        assert!(verify_body(&signed, &public_key(&signer), signature_id).unwrap());
+
+        let (_, rest) = split_signed_body(&signed).unwrap();
+        assert_eq!(rest.into_cell().repr_hash(), body().into_cell().repr_hash());
+    }
+}
+
+#[test]
+fn test_verify_body_wrong_signature() {
+    let signer = keypair(1);
+    // signature made with one id is not valid with another
+    let ids = [
+        (None, Some(GLOBAL_ID)),
+        (Some(GLOBAL_ID), None),
+        (Some(GLOBAL_ID), Some(GLOBAL_ID + 1)),
+    ];
+    for (sign_id, verify_id) in ids {
+        let signed = sign_body(&body(), &signer, sign_id).unwrap();
+        assert!(!verify_body(&signed, &public_key(&signer), verify_id).unwrap());
+    }
+
+    let signed = sign_body(&body(), &signer, Some(GLOBAL_ID)).unwrap();
+    assert!(!verify_body(&signed, &public_key(&keypair(2)), Some(GLOBAL_ID)).unwrap());
+
+    // body changed after signing
+    let (signature, _) = split_signed_body(&signed).unwrap();
+    let mut builder = BuilderData::new();
+    builder.append_raw(&signature.to_bytes(), 512).unwrap();
+    builder.append_u64(0).unwrap();
+    let forged = SliceData::load_builder(builder).unwrap();
+    assert!(!verify_body(&forged, &public_key(&signer), Some(GLOBAL_ID)).unwrap());
+}
+
+#[test]
+fn test_verify_body_too_short() {
+    let signer = keypair(1);
+    let mut builder = BuilderData::new();
+    builder.append_raw(&[0xFF; 64], 511).unwrap();
+    let short = SliceData::load_builder(builder).unwrap();
+    verify_body(&short, &public_key(&signer), None).expect_err("511 bits can't contain a signature");
+    split_signed_body(&SliceData::default()).expect_err("empty body can't contain a signature");
+}
@@ -0,0 +1,774 @@
+/*
+* Copyright 2018-2020 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use super::*;
+use crate::{
+    accounts::{generate_test_account_by_init_code_hash, StorageExtra},
+    blocks::{BlkPrevInfo, CopyleftRewards, OutQueueUpdates},
+    config_params::{
+        BridgeOracles, ConfigParamEnum, JettonBridgeFees, LicenseRates, MandatoryParams,
+        SizeLimitsAccState, WorkchainFormat, WorkchainFormat1,
+    },
+    envelope_message::{IntermediateAddressExt, IntermediateAddressSimple},
+    master::ShardIdentFull,
+    merkle_update::MerkleUpdate,
+    messages::{
+        AnycastInfo, ExtOutMessageHeader, ExternalInboundMessageHeader, InternalMessageHeader,
+        MsgAddrStd,
+    },
+    miscellaneous::ProcessedInfoKey,
+    shard::{AccountIdPrefixFull, ShardStateSplit},
+    transactions::{
+        TrComputePhaseVm, TransactionDescrMergeInstall, TransactionDescrMergePrepare,
+        TransactionDescrOrdinary, TransactionDescrSplitInstall, TransactionDescrSplitPrepare,
+        TransactionDescrTickTock, TransactionTickTock,
+    },
+    types::{Grams, Number16, Number5, VarUInteger3, VarUInteger32},
+    HashmapAugType,
+};
+use std::sync::Arc;
+use ton_types::{AccountId, UInt256};
+
+// value is serialized and the cell is parsed by the builtin schema to the end
+fn check<T: TlbType>(value: &T) {
+    assert!(!T::tlb_constructors().is_empty(), "`{}` is not in the schema", T::TLB_TYPE);
+    value.tlb_tree().unwrap_or_else(|err| panic!("`{}` doesn't match the schema: {}", T::TLB_TYPE, err));
+}
+
+fn cell(data: u32) -> Cell {
+    data.write_to_new_cell().unwrap().into_cell().unwrap()
+}
+
+fn hash(byte: u8) -> UInt256 {
+    UInt256::from([byte; 32])
+}
+
+fn account_id(byte: u8) -> AccountId {
+    AccountId::from([byte; 32])
+}
+
+fn address(byte: u8) -> MsgAddressInt {
+    MsgAddressInt::with_standart(None, 0, account_id(byte)).unwrap()
+}
+
+fn currencies(grams: u64) -> CurrencyCollection {
+    let mut value = CurrencyCollection::with_grams(grams);
+    value.set_other(7, 1000).unwrap();
+    value
+}
+
+fn int_message() -> Message {
+    let header = InternalMessageHeader::with_addresses(address(1), address(2), currencies(1_000_000));
+    let mut msg = Message::with_int_header(header);
+    msg.set_body(SliceData::new(vec![0xde, 0xad, 0xbe, 0xef, 0x80]));
+    msg
+}
+
+fn ext_in_message() -> Message {
+    let src = MsgAddressExt::with_extern(SliceData::new(vec![0x55, 0x80])).unwrap();
+    Message::with_ext_in_header(ExternalInboundMessageHeader::new(src, address(3)))
+}
+
+fn ext_out_message() -> Message {
+    Message::with_ext_out_header(ExtOutMessageHeader::with_addresses(address(3), MsgAddressExt::AddrNone))
+}
+
+fn envelope() -> MsgEnvelope {
+    MsgEnvelope::with_message_and_fee(&int_message(), Grams::from(1000u64)).unwrap()
+}
+
+fn action_phase() -> TrActionPhase {
+    TrActionPhase {
+        success: true,
+        valid: true,
+        status_change: AccStatusChange::Frozen,
+        total_fwd_fees: Some(Grams::from(100u64)),
+        result_arg: Some(-1),
+        tot_actions: 2,
+        msgs_created: 1,
+        action_list_hash: hash(5),
+        ..TrActionPhase::default()
+    }
+}
+
+fn vm_phase() -> TrComputePhase {
+    TrComputePhase::Vm(TrComputePhaseVm {
+        success: true,
+        gas_fees: Grams::from(1000u64),
+        gas_credit: Some(VarUInteger3::default()),
+        exit_code: 0,
+        exit_arg: Some(-2),
+        vm_steps: 10,
+        vm_init_state_hash: hash(6),
+        vm_final_state_hash: hash(7),
+        ..TrComputePhaseVm::default()
+    })
+}
+
+fn split_info() -> SplitMergeInfo {
+    SplitMergeInfo {
+        cur_shard_pfx_len: 3,
+        acc_split_depth: 5,
+        this_addr: hash(8),
+        sibling_addr: hash(9),
+    }
+}
+
+fn transaction(lt: u64) -> Transaction {
+    let mut tr = Transaction::with_address_and_status(account_id(2), AccountStatus::AccStateActive);
+    tr.set_logical_time(lt);
+    tr.set_now(1_600_000_000);
+    tr.write_in_msg(Some(&int_message())).unwrap();
+    tr.add_out_message(&int_message()).unwrap();
+    tr.add_out_message(&ext_out_message()).unwrap();
+    tr.set_total_fees(currencies(500));
+    tr.write_state_update(&HashUpdate::with_hashes(hash(10), hash(11))).unwrap();
+    tr.write_description(&TransactionDescr::Ordinary(TransactionDescrOrdinary {
+        credit_first: true,
+        storage_ph: Some(TrStoragePhase::with_params(Grams::from(5u64), Some(Grams::from(1u64)), AccStatusChange::Unchanged)),
+        credit_ph: Some(TrCreditPhase::with_params(Some(Grams::from(2u64)), currencies(100))),
+        compute_ph: vm_phase(),
+        action: Some(action_phase()),
+        aborted: false,
+        bounce: Some(TrBouncePhase::ok(StorageUsedShort::default(), Grams::from(3u64), Grams::from(4u64))),
+        destroyed: false,
+    })).unwrap();
+    tr
+}
+
+fn in_msg() -> InMsg {
+    let env = envelope();
+    InMsg::final_msg(env.serialize().unwrap(), transaction(10).serialize().unwrap(), *env.fwd_fee_remaining())
+}
+
+fn validator_set(since: u32) -> ValidatorSet {
+    let list = vec![
+        ValidatorDescr::with_params(SigPubKey::from_bytes(&[1; 32]).unwrap(), 17, None),
+        ValidatorDescr::with_params(SigPubKey::from_bytes(&[2; 32]).unwrap(), 5, Some(hash(3))),
+    ];
+    ValidatorSet::new(since, since + 1000, 1, list).unwrap()
+}
+
+fn config() -> ConfigParams {
+    let mut config = ConfigParams::new();
+    config.config_addr = hash(0x55);
+    config.set_config(ConfigParamEnum::ConfigParam19(ConfigParam19 { global_id: 42 })).unwrap();
+    config.set_config(ConfigParamEnum::ConfigParam34(ConfigParam34 { cur_validators: validator_set(100) })).unwrap();
+    config
+}
+
+fn shard_hashes() -> ShardHashes {
+    let mut shards = ShardHashes::default();
+    shards.add_workchain(0, 10, hash(12), hash(13)).unwrap();
+    shards
+}
+
+fn block_signatures() -> BlockSignatures {
+    let mut signatures = BlockSignaturesPure::default();
+    signatures.add_sigpair(CryptoSignaturePair::with_params(hash(14), CryptoSignature::default()));
+    BlockSignatures::with_params(ValidatorBaseInfo::with_params(0x1234, 5), signatures)
+}
+
+fn mc_block_extra() -> McBlockExtra {
+    let mut extra = McBlockExtra::default();
+    *extra.shards_mut() = shard_hashes();
+    extra.fees_mut().store_shard_fees(&ShardIdent::default(), currencies(10), currencies(20)).unwrap();
+    extra.prev_blk_signatures_mut().set(&0u16, &CryptoSignaturePair::with_params(hash(15), CryptoSignature::default())).unwrap();
+    extra.write_recover_create_msg(Some(&in_msg())).unwrap();
+    extra.write_mint_msg(Some(&in_msg())).unwrap();
+    extra.set_config(config());
+    extra
+}
+
+fn mc_state_extra() -> McStateExtra {
+    let mut prev_blocks = OldMcBlocksInfo::default();
+    let key_ref = KeyExtBlkRef { key: true, blk_ref: ExtBlkRef { end_lt: 100, seq_no: 1, ..ExtBlkRef::default() } };
+    prev_blocks.set(&1u32, &key_ref, &KeyMaxLt { key: true, max_end_lt: 100 }).unwrap();
+    let mut block_create_stats = BlockCreateStats::default();
+    block_create_stats.counters.set(&hash(16), &CreatorStats::default()).unwrap();
+    let mut state_copyleft_rewards = CopyleftRewards::default();
+    state_copyleft_rewards.add_copyleft_reward(&account_id(17), &Grams::from(100u64)).unwrap();
+    McStateExtra {
+        shards: shard_hashes(),
+        config: config(),
+        validator_info: ValidatorInfo::with_params(0x1234, 5, true),
+        prev_blocks,
+        after_key_block: true,
+        last_key_block: Some(key_ref.blk_ref.clone()),
+        block_create_stats: Some(block_create_stats),
+        global_balance: currencies(1_000_000_000),
+        state_copyleft_rewards,
+    }
+}
+
+fn out_msg_queue_info() -> OutMsgQueueInfo {
+    let mut out_queue = OutMsgQueue::default();
+    out_queue.insert(0, 0x8000_0000_0000_0000, &envelope(), 20).unwrap();
+    let mut proc_info = ProcessedInfo::default();
+    proc_info.set(&ProcessedInfoKey::with_params(0x8000_0000_0000_0000, 3), &ProcessedUpto::default()).unwrap();
+    OutMsgQueueInfo::with_params(out_queue, proc_info, IhrPendingInfo::default())
+}
+
+fn shard_state(shard: ShardIdent) -> ShardStateUnsplit {
+    let mut accounts = ShardAccounts::default();
+    let account = generate_test_account_by_init_code_hash(false);
+    accounts.insert(0, &account, hash(18), 30).unwrap();
+    let mut state = ShardStateUnsplit::with_ident(shard);
+    state.set_global_id(42);
+    state.set_seq_no(7);
+    state.write_out_msg_queue_info(&out_msg_queue_info()).unwrap();
+    state.write_accounts(&accounts).unwrap();
+    state.set_total_balance(currencies(1_000_000));
+    state.libraries_mut().set(&hash(19), &LibDescr::from_lib_data_by_publisher(cell(19), account_id(20))).unwrap();
+    state.set_master_ref(Some(BlkMasterInfo::default()));
+    state
+}
+
+fn block_info() -> BlockInfo {
+    let mut info = BlockInfo::new();
+    info.set_shard(ShardIdent::default());
+    info.set_seq_no(12).unwrap();
+    info.set_gen_software(Some(GlobalVersion { version: 45, capabilities: 0x46e }));
+    info.write_master_ref(Some(&BlkMasterInfo { master: ExtBlkRef { seq_no: 3, ..ExtBlkRef::default() } })).unwrap();
+    info.set_prev_stuff(true, &BlkPrevInfo::new(vec![ExtBlkRef::default(), ExtBlkRef::default()]).unwrap()).unwrap();
+    info.set_vertical_stuff(1, 1, Some(BlkPrevInfo::default_block())).unwrap();
+    info
+}
+
+fn block_extra() -> BlockExtra {
+    let env = envelope();
+    let tr = transaction(10);
+    let mut in_msgs = InMsgDescr::default();
+    in_msgs.insert(&in_msg()).unwrap();
+    in_msgs.insert(&InMsg::external(ext_in_message().serialize().unwrap(), tr.serialize().unwrap())).unwrap();
+    let mut out_msgs = OutMsgDescr::default();
+    out_msgs.insert(&OutMsg::new(env.serialize().unwrap(), tr.serialize().unwrap())).unwrap();
+    let mut account_blocks = ShardAccountBlocks::default();
+    account_blocks.add_transaction(&tr).unwrap();
+    let mut extra = BlockExtra::new();
+    extra.write_in_msg_descr(&in_msgs).unwrap();
+    extra.write_out_msg_descr(&out_msgs).unwrap();
+    extra.write_account_blocks(&account_blocks).unwrap();
+    extra.write_custom(Some(&mc_block_extra())).unwrap();
+    extra
+}
+
+#[test]
+fn test_builtin_schema() {
+    let schema = TlbSchema::builtin();
+    assert_eq!(schema.type_names(), TlbSchema::parse(BLOCK_TLB).unwrap().type_names());
+    for name in ["Message", "Account", "Transaction", "Block", "ShardStateUnsplit", "ConfigParam"] {
+        assert!(schema.type_names().iter().any(|type_name| type_name == name), "no type `{}`", name);
+    }
+    for name in schema.type_names() {
+        assert!(!schema.constructors(name).unwrap().is_empty());
+        assert!(!schema.definition(name).unwrap().is_empty());
+    }
+    assert_eq!(Message::tlb_type_name(), "Message");
+    assert!(HashUpdate::tlb_definition().starts_with("update_hashes#72"));
+}
+
+#[test]
+fn test_repeated_zero_width_item() {
+    let schema = TlbSchema::parse("true$_ = True; _ n:(## 8) items:(n * True) = Items;").unwrap();
+    schema.parse_cell(&0u8.write_to_new_cell().unwrap().into_cell().unwrap(), "Items").unwrap();
+    schema.parse_cell(&200u8.write_to_new_cell().unwrap().into_cell().unwrap(), "Items")
+        .expect_err("repeated item without data must fail");
+}
+
+#[test]
+fn test_messages_and_accounts() {
+    check(&MsgAddressExt::AddrNone);
+    check(&MsgAddressExt::with_extern(SliceData::new(vec![0x12, 0x34, 0x80])).unwrap());
+    let anycast = AnycastInfo::with_rewrite_pfx(SliceData::new(vec![0b1011_0000])).unwrap();
+    check(&MsgAddressInt::with_standart(Some(anycast.clone()), -1, account_id(1)).unwrap());
+    check(&MsgAddressInt::with_variant(Some(anycast), 1000, SliceData::new(vec![0x12, 0x34, 0x80])).unwrap());
+    check(&MsgAddress::AddrStd(MsgAddrStd::with_address(None, 0, account_id(2))));
+    check(&MsgAddress::AddrNone);
+    check(&ExtraCurrencyCollection::default());
+    check(&currencies(100));
+    check(&CommonMsgInfo::default());
+    check(int_message().header());
+    check(ext_in_message().header());
+    check(ext_out_message().header());
+    check(&TickTock::with_values(true, false));
+    check(&SimpleLib::new(cell(1), true));
+    check(&StateInit::default());
+
+    let mut state_init = StateInit::default();
+    state_init.set_split_depth(Number5::new(5).unwrap());
+    state_init.set_special(TickTock::with_values(false, true));
+    state_init.set_code(cell(2));
+    state_init.set_data(cell(3));
+    state_init.set_library_code(cell(4), false).unwrap();
+    check(&state_init);
+
+    check(&Message::default());
+    check(&int_message());
+    check(&ext_in_message());
+    check(&ext_out_message());
+    let mut msg = int_message();
+    msg.set_state_init(state_init.clone());
+    check(&msg);
+    msg.set_body(SliceData::load_cell(cell(0x1234)).unwrap());
+    check(&msg);
+
+    check(&StorageUsed::default());
+    check(&StorageUsed::with_values_checked(3, 700, StorageExtra::Dict { dict_hash: hash(1) }).unwrap());
+    check(&StorageInfo::with_values(1_600_000_000, Some(Grams::from(10u64))));
+    check(&AccountState::AccountUninit);
+    check(&AccountState::AccountFrozen { state_init_hash: hash(2) });
+    check(&AccountState::AccountActive { state_init });
+    for status in [
+        AccountStatus::AccStateUninit,
+        AccountStatus::AccStateFrozen,
+        AccountStatus::AccStateActive,
+        AccountStatus::AccStateNonexist,
+    ] {
+        check(&status);
+    }
+    check(&Account::default());
+    let account = generate_test_account_by_init_code_hash(false);
+    check(&account);
+    check(&generate_test_account_by_init_code_hash(true));
+    check(&ShardAccount::with_params(&account, hash(3), 10).unwrap());
+    check(&DepthBalanceInfo::new(5, &currencies(200)).unwrap());
+    let mut accounts = ShardAccounts::default();
+    check(&accounts);
+    accounts.insert(0, &account, hash(4), 20).unwrap();
+    check(&accounts);
+}
+
+#[test]
+fn test_validators_and_signatures() {
+    check(&ShardIdent::masterchain());
+    check(&ShardIdent::with_tagged_prefix(0, 0x6000_0000_0000_0000).unwrap());
+    check(&BlockIdExt::with_params(ShardIdent::default(), 10, hash(1), hash(2)));
+    check(&ExtBlkRef { end_lt: 100, seq_no: 10, root_hash: hash(3), file_hash: hash(4) });
+    check(&SigPubKey::from_bytes(&[5; 32]).unwrap());
+    let mut descr = ValidatorDescr::with_params(SigPubKey::from_bytes(&[6; 32]).unwrap(), 10, Some(hash(7)));
+    check(&descr);
+    descr.mc_seq_no_since = 100;
+    check(&descr);
+    check(&validator_set(100));
+    check(&config());
+    check(&GlobalVersion { version: 45, capabilities: 0x46e });
+    let param = ConfigParamEnum::ConfigParam19(ConfigParam19 { global_id: 43 });
+    check(&ConfigProposal::with_param(&param).unwrap().with_hash_check(&config()).unwrap());
+    check(&ConfigProposal::with_removal(19));
+    check(&CryptoSignature::default());
+    check(&CryptoSignaturePair::with_params(hash(8), CryptoSignature::default()));
+    check(&ValidatorBaseInfo::with_params(0x1234, 5));
+    check(&ValidatorInfo::with_params(0x1234, 5, true));
+    check(&BlockSignaturesPure::default());
+    check(&block_signatures().pure_signatures);
+    check(&block_signatures());
+    let block_id = BlockIdExt::with_params(ShardIdent::masterchain(), 10, hash(9), hash(10));
+    check(&BlockProof::with_params(block_id.clone(), cell(11), None));
+    check(&BlockProof::with_params(block_id, cell(11), Some(block_signatures())));
+}
+
+#[test]
+fn test_transactions() {
+    check(&AccStatusChange::Unchanged);
+    check(&AccStatusChange::Frozen);
+    check(&AccStatusChange::Deleted);
+    for reason in [
+        ComputeSkipReason::NoState,
+        ComputeSkipReason::BadState,
+        ComputeSkipReason::NoGas,
+        ComputeSkipReason::Suspended,
+    ] {
+        check(&reason);
+        check(&TrComputePhase::skipped(reason));
+    }
+    check(&StorageUsedShort::default());
+    check(&TrStoragePhase::default());
+    check(&TrStoragePhase::with_params(Grams::from(1u64), Some(Grams::from(2u64)), AccStatusChange::Deleted));
+    check(&TrCreditPhase::default());
+    check(&TrCreditPhase::with_params(Some(Grams::from(3u64)), currencies(4)));
+    check(&vm_phase());
+    check(&TrActionPhase::default());
+    check(&action_phase());
+    check(&TrBouncePhase::Negfunds);
+    check(&TrBouncePhase::no_funds(StorageUsedShort::default(), Grams::from(5u64)));
+    check(&TrBouncePhase::ok(StorageUsedShort::default(), Grams::from(6u64), Grams::from(7u64)));
+    check(&split_info());
+    check(&HashUpdate::with_hashes(hash(1), hash(2)));
+
+    let prepare = Arc::new(transaction(5));
+    let descriptions = [
+        TransactionDescr::default(),
+        TransactionDescr::Storage(TrStoragePhase::with_params(Grams::from(1u64), None, AccStatusChange::Frozen)),
+        TransactionDescr::TickTock(TransactionDescrTickTock {
+            tt: TransactionTickTock::Tock,
+            compute_ph: vm_phase(),
+            action: Some(action_phase()),
+            ..TransactionDescrTickTock::default()
+        }),
+        TransactionDescr::SplitPrepare(TransactionDescrSplitPrepare {
+            split_info: split_info(),
+            compute_ph: vm_phase(),
+            action: Some(action_phase()),
+            aborted: true,
+            destroyed: false,
+        }),
+        TransactionDescr::SplitInstall(TransactionDescrSplitInstall {
+            split_info: split_info(),
+            prepare_transaction: prepare.clone(),
+            installed: true,
+        }),
+        TransactionDescr::MergePrepare(TransactionDescrMergePrepare {
+            split_info: split_info(),
+            storage_ph: TrStoragePhase::default(),
+            aborted: false,
+        }),
+        TransactionDescr::MergeInstall(TransactionDescrMergeInstall {
+            split_info: split_info(),
+            prepare_transaction: prepare,
+            credit_ph: Some(TrCreditPhase::default()),
+            compute_ph: TrComputePhase::default(),
+            action: None,
+            aborted: false,
+            destroyed: true,
+        }),
+    ];
+    for descr in descriptions {
+        check(&descr);
+    }
+
+    let tr = transaction(10);
+    check(&tr);
+    let account_block = AccountBlock::with_transaction(account_id(2), &tr).unwrap();
+    check(&account_block);
+    let mut account_blocks = ShardAccountBlocks::default();
+    check(&account_blocks);
+    account_blocks.add_transaction(&tr).unwrap();
+    check(&account_blocks);
+}
+
+#[test]
+fn test_message_descriptors_and_queues() {
+    check(&IntermediateAddress::full_dest());
+    check(&IntermediateAddress::use_dest_bits(96).unwrap());
+    check(&IntermediateAddress::Simple(IntermediateAddressSimple::with_addr(-1, 0x8000_0000_0000_0000)));
+    check(&IntermediateAddress::Ext(IntermediateAddressExt::with_addr(1000, 0x4000_0000_0000_0000)));
+    let env = envelope();
+    check(&env);
+    check(&ImportFees::with_grams(100));
+
+    let env_cell = env.serialize().unwrap();
+    let tr_cell = transaction(10).serialize().unwrap();
+    let msg_cell = ext_in_message().serialize().unwrap();
+    let fee = Grams::from(1000u64);
+    let in_msgs = [
+        InMsg::external(msg_cell, tr_cell.clone()),
+        InMsg::ihr(int_message().serialize().unwrap(), tr_cell.clone(), fee, cell(1)),
+        InMsg::immediate(env_cell.clone(), tr_cell.clone(), fee),
+        InMsg::final_msg(env_cell.clone(), tr_cell.clone(), fee),
+        InMsg::transit(env_cell.clone(), env_cell.clone(), fee),
+        InMsg::discarded_final(env_cell.clone(), 10, fee),
+        InMsg::discarded_transit(env_cell.clone(), 10, fee, cell(2)),
+    ];
+    for msg in &in_msgs {
+        check(msg);
+    }
+    let mut in_msg_descr = InMsgDescr::default();
+    check(&in_msg_descr);
+    in_msg_descr.insert(&in_msgs[0]).unwrap();
+    in_msg_descr.insert(&in_msgs[3]).unwrap();
+    check(&in_msg_descr);
+
+    let reimport_cell = in_msgs[2].serialize().unwrap();
+    let prefix = AccountIdPrefixFull { workchain_id: 0, prefix: 0x8000_0000_0000_0000 };
+    let out_msgs = [
+        OutMsg::external(ext_out_message().serialize().unwrap(), tr_cell.clone()),
+        OutMsg::new(env_cell.clone(), tr_cell.clone()),
+        OutMsg::immediate(env_cell.clone(), tr_cell.clone(), reimport_cell.clone()),
+        OutMsg::transit(env_cell.clone(), reimport_cell.clone(), false),
+        OutMsg::transit(env_cell.clone(), reimport_cell.clone(), true),
+        OutMsg::dequeue_immediate(env_cell.clone(), reimport_cell),
+        OutMsg::dequeue_long(env_cell.clone(), 20),
+        OutMsg::dequeue_short(env_cell.repr_hash(), &prefix, 20),
+    ];
+    for msg in &out_msgs {
+        check(msg);
+    }
+    let mut out_msg_descr = OutMsgDescr::default();
+    check(&out_msg_descr);
+    out_msg_descr.insert(&out_msgs[0]).unwrap();
+    out_msg_descr.insert(&out_msgs[1]).unwrap();
+    check(&out_msg_descr);
+
+    check(&EnqueuedMsg::with_param(20, &env).unwrap());
+    check(&OutMsgQueue::default());
+    check(out_msg_queue_info().out_queue());
+    check(&ProcessedUpto::default());
+    check(out_msg_queue_info().proc_info());
+    check(&IhrPendingSince::with_import_lt(30));
+    check(&IhrPendingInfo::default());
+    check(&OutMsgQueueInfo::default());
+    check(&out_msg_queue_info());
+}
+
+#[test]
+fn test_blocks_and_states() {
+    check(&BlkMasterInfo::default());
+    check(&BlockInfo::default());
+    check(&block_info());
+    check(&ValueFlow::default());
+    let mut value_flow = ValueFlow {
+        from_prev_blk: currencies(1000),
+        to_next_blk: currencies(1100),
+        fees_collected: currencies(10),
+        created: currencies(110),
+        ..ValueFlow::default()
+    };
+    check(&value_flow);
+    value_flow.copyleft_rewards.add_copyleft_reward(&account_id(1), &Grams::from(5u64)).unwrap();
+    check(&value_flow);
+
+    let block_id = BlockIdExt::with_params(ShardIdent::default(), 10, hash(1), hash(2));
+    let mut top_block = TopBlockDescr::with_id_and_signatures(block_id, block_signatures());
+    top_block.append_proof(cell(3));
+    top_block.append_proof(cell(4));
+    check(&top_block);
+    let mut top_blocks = TopBlockDescrSet::default();
+    check(&top_blocks);
+    top_blocks.insert(&ShardIdent::default(), &top_block).unwrap();
+    check(&top_blocks);
+
+    check(&FutureSplitMerge::None);
+    check(&FutureSplitMerge::Split { split_utime: 100, interval: 10 });
+    check(&FutureSplitMerge::Merge { merge_utime: 100, interval: 10 });
+    let mut shard_descr = ShardDescr::with_params(10, 100, 200, hash(5), FutureSplitMerge::None);
+    shard_descr.before_split = true;
+    shard_descr.fees_collected = currencies(10);
+    check(&shard_descr);
+    shard_descr.copyleft_rewards.add_copyleft_reward(&account_id(6), &Grams::from(5u64)).unwrap();
+    check(&shard_descr);
+    check(&ShardHashes::default());
+    check(&shard_hashes());
+    let fee_created = ShardFeeCreated::with_fee(currencies(10));
+    check(&fee_created);
+    let mut shard_fees = ShardFees::default();
+    check(&shard_fees);
+    shard_fees.set(&ShardIdentFull::new(0, 0x8000_0000_0000_0000), &fee_created, &fee_created).unwrap();
+    check(&shard_fees);
+    check(&McBlockExtra::default());
+    check(&mc_block_extra());
+
+    check(&KeyMaxLt { key: true, max_end_lt: 100 });
+    check(&KeyExtBlkRef::default());
+    check(&OldMcBlocksInfo::default());
+    check(&mc_state_extra().prev_blocks);
+    check(&Counters::default());
+    check(&CreatorStats::default());
+    check(&BlockCreateStats::default());
+    check(mc_state_extra().block_create_stats.as_ref().unwrap());
+    check(&McStateExtra { config: config(), ..McStateExtra::default() });
+    check(&mc_state_extra());
+
+    check(&BlockExtra::new());
+    let extra = block_extra();
+    check(&extra);
+    check(&OutQueueUpdate::default());
+    let block = Block::with_params(42, block_info(), value_flow.clone(), MerkleUpdate::default(), extra.clone()).unwrap();
+    check(&block);
+    let mut updates = OutQueueUpdates::default();
+    updates.set(&0i32, &OutQueueUpdate { is_empty: true, update: MerkleUpdate::default() }).unwrap();
+    let block = Block::with_out_queue_updates(
+        42, block_info(), value_flow, MerkleUpdate::default(), Some(updates), extra
+    ).unwrap();
+    check(&block);
+
+    check(&LibDescr::from_lib_data_by_publisher(cell(7), account_id(8)));
+    let mut state = shard_state(ShardIdent::masterchain());
+    check(&state);
+    state.write_custom(Some(&mc_state_extra())).unwrap();
+    check(&state);
+    check(&ShardState::UnsplitState(state));
+    let (left, right) = ShardIdent::default().split().unwrap();
+    let split = ShardStateSplit::with_states(&shard_state(left), &shard_state(right)).unwrap();
+    check(&ShardState::SplitState(split));
+}
+
+#[test]
+fn test_config_params() {
+    check(&ConfigParam0 { config_addr: hash(0x10) });
+    check(&ConfigParam1 { elector_addr: hash(0x11) });
+    check(&ConfigParam2 { minter_addr: hash(0x12) });
+    check(&ConfigParam3 { fee_collector_addr: hash(0x13) });
+    check(&ConfigParam4 { dns_root_addr: hash(0x14) });
+    check(&ConfigParam5 { owner_addr: hash(0x15) });
+    check(&ConfigParam6 { mint_new_price: Grams::from(1000u64), mint_add_price: Grams::from(100u64) });
+    let mut to_mint = ExtraCurrencyCollection::default();
+    to_mint.set(&239u32, &VarUInteger32::from_two_u128(0, 666).unwrap()).unwrap();
+    check(&ConfigParam7 { to_mint });
+    check(&ConfigParam8 { global_version: GlobalVersion { version: 45, capabilities: 0x46e } });
+    let mut mandatory_params = MandatoryParams::default();
+    for index in [0u32, 1, 9, 10, 12, 14] {
+        mandatory_params.add_key(&index).unwrap();
+    }
+    check(&ConfigParam9 { mandatory_params: mandatory_params.clone() });
+    check(&ConfigParam10 { critical_params: mandatory_params });
+
+    let setup = ConfigProposalSetup {
+        min_tot_rounds: 2,
+        max_tot_rounds: 3,
+        min_wins: 2,
+        max_losses: 1,
+        min_store_sec: 1000,
+        max_store_sec: 100000,
+        bit_price: 1,
+        cell_price: 500,
+    };
+    check(&setup);
+    check(&ConfigVotingSetup::new(&setup, &setup).unwrap());
+
+    let mut workchain = WorkchainDescr {
+        enabled_since: 1_573_821_854,
+        active: true,
+        accept_msgs: true,
+        zerostate_root_hash: hash(4),
+        zerostate_file_hash: hash(5),
+        format: WorkchainFormat::Basic(WorkchainFormat1 { vm_version: 0, vm_mode: 0 }),
+        ..WorkchainDescr::default()
+    };
+    workchain.set_min_split(2).unwrap();
+    workchain.set_max_split(32).unwrap();
+    workchain.set_actual_min_split(2).unwrap();
+    check(&workchain);
+    let mut param12 = ConfigParam12::new();
+    param12.insert(0, &workchain).unwrap();
+    check(&param12);
+    check(&ConfigParam13 { cell: cell(13) });
+
+    let block_create_fees = BlockCreateFees {
+        masterchain_block_fee: Grams::from(1_700_000_000u64),
+        basechain_block_fee: Grams::from(1_000_000_000u64),
+    };
+    check(&block_create_fees);
+    check(&ConfigParam14 { block_create_fees });
+    check(&ConfigParam15 {
+        validators_elected_for: 65536,
+        elections_start_before: 32768,
+        elections_end_before: 8192,
+        stake_held_for: 32768,
+    });
+    check(&ConfigParam16 {
+        max_validators: Number16::new(1000).unwrap(),
+        max_main_validators: Number16::new(100).unwrap(),
+        min_validators: Number16::new(13).unwrap(),
+    });
+    check(&ConfigParam17 {
+        min_stake: Grams::from(10u64),
+        max_stake: Grams::from(1000u64),
+        min_total_stake: Grams::from(100u64),
+        max_stake_factor: 196608,
+    });
+    let storage_prices = StoragePrices {
+        utime_since: 0,
+        bit_price_ps: 1,
+        cell_price_ps: 500,
+        mc_bit_price_ps: 1000,
+        mc_cell_price_ps: 500000,
+    };
+    check(&storage_prices);
+    let mut param18 = ConfigParam18::default();
+    param18.insert(&storage_prices).unwrap();
+    check(&param18);
+    check(&ConfigParam19 { global_id: -239 });
+
+    check(&GasLimitsPrices { gas_price: 1000, gas_limit: 100, flat_gas_limit: 10, ..GasLimitsPrices::default() });
+    let limits = ParamLimits::with_limits(1000, 2000, 3000).unwrap();
+    check(&limits);
+    check(&BlockLimits::with_limits(limits.clone(), limits.clone(), limits));
+    check(&MsgForwardPrices { lump_price: 1000, bit_price: 1 << 16, first_frac: 21845, ..MsgForwardPrices::default() });
+    check(&CatchainConfig::default());
+    check(&CatchainConfig { shuffle_mc_validators: true, mc_catchain_lifetime: 250, ..CatchainConfig::default() });
+    check(&ConsensusConfig::default());
+    let consensus_config = ConsensusConfig { new_catchain_ids: true, round_candidates: 3, ..ConsensusConfig::default() };
+    check(&consensus_config);
+    check(&ConfigParam29 { consensus_config });
+    check(&DelectorParams { delections_step: 10, validator_init_code_hash: hash(0x30), staker_init_code_hash: hash(0x31) });
+    let mut param31 = ConfigParam31::new();
+    param31.add_address(hash(0x33));
+    check(&param31);
+    check(&ConfigParam32 { prev_validators: validator_set(100) });
+    check(&ConfigParam33 { prev_temp_validators: validator_set(200) });
+    check(&ConfigParam34 { cur_validators: validator_set(300) });
+    check(&ConfigParam35 { cur_temp_validators: validator_set(400) });
+    check(&ConfigParam36 { next_validators: validator_set(500) });
+    check(&ConfigParam37 { next_temp_validators: validator_set(600) });
+
+    let temp_key = ValidatorTempKey::with_params(hash(6), SigPubKey::from_bytes(&[7; 32]).unwrap(), 3, 1_600_000_000);
+    check(&temp_key);
+    let signed_key = ValidatorSignedTempKey::with_key_and_signature(temp_key, CryptoSignature::default());
+    check(&signed_key);
+    let mut param39 = ConfigParam39::new();
+    param39.insert(&hash(8), &signed_key).unwrap();
+    check(&param39);
+    check(&SlashingConfig::new());
+    check(&ConfigParam40 { slashing_config: SlashingConfig::new() });
+
+    let mut license_rates = LicenseRates::default();
+    license_rates.set(&0u8, &50u8).unwrap();
+    check(&ConfigCopyleft { copyleft_reward_threshold: Grams::from(100u64), license_rates });
+    let mut size_limits = SizeLimitsConfig {
+        max_msg_bits: 1 << 21,
+        max_msg_cells: 1 << 13,
+        max_library_cells: 1000,
+        max_vm_data_depth: 512,
+        max_ext_msg_size: 65535,
+        max_ext_msg_depth: 512,
+        acc_state_limits: None,
+    };
+    check(&size_limits);
+    size_limits.acc_state_limits = Some(SizeLimitsAccState {
+        max_acc_state_cells: 1 << 16,
+        max_acc_state_bits: 1 << 25,
+        max_acc_public_libraries: 256,
+        defer_out_queue_size_limit: 256,
+    });
+    check(&size_limits);
+    check(&PrecompiledSmc::with_gas_usage(1000));
+    let mut precompiled = PrecompiledContractsConfig::new();
+    precompiled.insert(&hash(0x99), &PrecompiledSmc::with_gas_usage(1000)).unwrap();
+    check(&precompiled);
+
+    let mut oracles = BridgeOracles::default();
+    oracles.set(&hash(7), &hash(8)).unwrap();
+    check(&OracleBridgeParams {
+        bridge_address: hash(0x71),
+        oracle_multisig_address: hash(0x72),
+        oracles: oracles.clone(),
+        external_chain_address: hash(0x73),
+    });
+    let prices = JettonBridgePrices {
+        bridge_burn_fee: Grams::from(1u64),
+        bridge_mint_fee: Grams::from(2u64),
+        ..JettonBridgePrices::default()
+    };
+    check(&prices);
+    let mut jetton_bridge = JettonBridgeParams {
+        bridge_address: hash(0x79),
+        oracles_address: hash(0x80),
+        oracles,
+        state_flags: 1,
+        fees: JettonBridgeFees::V0 { burn_bridge_fee: Grams::from(1000u64) },
+    };
+    check(&jetton_bridge);
+    jetton_bridge.fees = JettonBridgeFees::V1 { prices, external_chain_address: hash(0x81) };
+    check(&jetton_bridge);
+}
@@ -0,0 +1,1293 @@
+/*
+* Copyright 2018-2020 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use std::{collections::HashMap, fmt, sync::OnceLock};
+
+use serde_json::{Map, Value};
+use ton_types::{error, fail, Cell, Result, SliceData};
+
+use crate::{
+    accounts::{
+        Account, AccountState, AccountStatus, ShardAccount, StorageInfo, StorageUsed,
+        StorageUsedShort,
+    },
+    blocks::{
+        Block, BlockExtra, BlockIdExt, BlockInfo, ExtBlkRef, OutQueueUpdate, TopBlockDescr,
+        TopBlockDescrSet, ValueFlow,
+    },
+    config_params::{
+        BlockCreateFees, BlockLimits, CatchainConfig, ConfigCopyleft, ConfigParam0, ConfigParam1,
+        ConfigParam10, ConfigParam12, ConfigParam13, ConfigParam14, ConfigParam15, ConfigParam16,
+        ConfigParam17, ConfigParam18, ConfigParam19, ConfigParam2, ConfigParam29, ConfigParam3,
+        ConfigParam31, ConfigParam32, ConfigParam33, ConfigParam34, ConfigParam35, ConfigParam36,
+        ConfigParam37, ConfigParam39, ConfigParam4, ConfigParam40, ConfigParam5, ConfigParam6,
+        ConfigParam7, ConfigParam8, ConfigParam9, ConfigParams, ConfigProposalSetup,
+        ConfigVotingSetup, ConsensusConfig, DelectorParams, GasLimitsPrices, GlobalVersion,
+        JettonBridgeParams, JettonBridgePrices, MsgForwardPrices, OracleBridgeParams, ParamLimits,
+        PrecompiledContractsConfig, PrecompiledSmc, SizeLimitsConfig, SlashingConfig,
+        StoragePrices, ValidatorSignedTempKey, ValidatorTempKey, WorkchainDescr,
+    },
+    config_proposal::ConfigProposal,
+    envelope_message::{IntermediateAddress, MsgEnvelope},
+    error::BlockError,
+    inbound_messages::{ImportFees, InMsg, InMsgDescr},
+    master::{
+        BlkMasterInfo, BlockCreateStats, Counters, CreatorStats, FutureSplitMerge, KeyExtBlkRef,
+        KeyMaxLt, LibDescr, McBlockExtra, McStateExtra, OldMcBlocksInfo, ShardDescr,
+        ShardFeeCreated, ShardFees, ShardHashes,
+    },
+    messages::{
+        CommonMsgInfo, Message, MsgAddress, MsgAddressExt, MsgAddressInt, SimpleLib, StateInit,
+        TickTock,
+    },
+    miscellaneous::{IhrPendingInfo, IhrPendingSince, ProcessedInfo, ProcessedUpto},
+    outbound_messages::{EnqueuedMsg, OutMsg, OutMsgDescr, OutMsgQueue, OutMsgQueueInfo},
+    shard::{ShardIdent, ShardState, ShardStateUnsplit},
+    shard_accounts::{DepthBalanceInfo, ShardAccounts},
+    signature::{
+        BlockProof, BlockSignatures, BlockSignaturesPure, CryptoSignature, CryptoSignaturePair,
+        SigPubKey,
+    },
+    transactions::{
+        AccStatusChange, AccountBlock, ComputeSkipReason, HashUpdate, ShardAccountBlocks,
+        SplitMergeInfo, TrActionPhase, TrBouncePhase, TrComputePhase, TrCreditPhase,
+        TrStoragePhase, Transaction, TransactionDescr,
+    },
+    types::{CurrencyCollection, ExtraCurrencyCollection},
+    validators::{ValidatorBaseInfo, ValidatorDescr, ValidatorInfo, ValidatorSet},
+    Serializable,
+};
+
+// TL-B schema of the crate types, see TlbSchema::builtin
+const BLOCK_TLB: &str = include_str!("block.tlb");
+
+///
+/// Type expression of TL-B schema
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TlbExpr {
+    /// type (or natural parameter) with arguments, e.g. `HashmapE 32 X`
+    Apply(String, Vec<TlbExpr>),
+    /// natural number literal
+    Nat(u32),
+    /// `^X` - value is stored in the next reference
+    Ref(Box<TlbExpr>),
+    /// anonymous constructor `[ fields ]`
+    Anon(Vec<TlbField>),
+    /// `a * b` - multiplication or repetition of type `b`
+    Mul(Box<TlbExpr>, Box<TlbExpr>),
+    /// `a + b`
+    Add(Box<TlbExpr>, Box<TlbExpr>),
+    /// `#<= n` - natural number not greater than n
+    NatLeq(Box<TlbExpr>),
+    /// `#< n` - natural number less than n
+    NatLess(Box<TlbExpr>),
+    /// `## n` - natural number of n bits
+    NatBits(Box<TlbExpr>),
+    /// `flag?X` or `flags.bit?X` - value is present if flag (or its bit) is set
+    Cond(String, Option<u32>, Box<TlbExpr>),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TlbField {
+    /// None for anonymous fields
+    pub name: Option<String>,
+    pub expr: TlbExpr,
+}
+
+///
+/// Constructor tag: len high bits of the value are written before fields
+///
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct TlbTag {
+    pub bits: u64,
+    pub len: usize,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TlbConstructor {
+    pub name: String,
+    pub tag: TlbTag,
+    /// implicit parameters `{name:kind}`
+    pub params: Vec<(String, String)>,
+    pub fields: Vec<TlbField>,
+    pub type_name: String,
+    pub type_args: Vec<TlbExpr>,
+}
+
+///
+/// Generic value parsed from a cell by TL-B schema
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TlbValue {
+    /// absent optional value or value of empty type
+    Null,
+    Bool(bool),
+    /// unsigned number up to 128 bits
+    Nat(u128),
+    /// signed number up to 128 bits
+    Int(i128),
+    /// bit string (and numbers longer than 128 bits)
+    Bits { data: Vec<u8>, len: usize },
+    /// cell which is not parsed (Cell and Any types)
+    Cell(Cell),
+    Either { right: bool, value: Box<TlbValue> },
+    /// repeated values
+    List(Vec<TlbValue>),
+    /// value of constructor, type_name is empty for anonymous constructors
+    Struct { type_name: String, constructor: String, fields: Vec<(String, TlbValue)> },
+    /// dictionary, extra is the root augmentation of HashmapAugE
+    Map { entries: Vec<TlbMapEntry>, extra: Option<Box<TlbValue>> },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TlbMapEntry {
+    /// key bits
+    pub key: Vec<u8>,
+    pub value: TlbValue,
+    /// augmentation of the leaf for HashmapAug
+    pub extra: Option<TlbValue>,
+}
+
+impl TlbValue {
+    /// Get field of parsed constructor by name
+    pub fn field(&self, name: &str) -> Option<&TlbValue> {
+        match self {
+            TlbValue::Struct { fields, .. } => fields.iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| value),
+            _ => None
+        }
+    }
+
+    pub fn as_nat(&self) -> Option<u128> {
+        match self {
+            TlbValue::Nat(value) => Some(*value),
+            TlbValue::Bool(value) => Some(*value as u128),
+            _ => None
+        }
+    }
+
+    ///
+    /// Convert parsed tree to JSON.
+    /// Constructors are written as objects with `@type` field, numbers longer than 64 bits
+    /// are written as decimal strings, bit strings as hex (with `:len` suffix if not aligned),
+    /// cells as base64 encoded bag of cells.
+    ///
+    pub fn to_json(&self) -> Result<Value> {
+        Ok(match self {
+            TlbValue::Null => Value::Null,
+            TlbValue::Bool(value) => Value::Bool(*value),
+            TlbValue::Nat(value) => match u64::try_from(*value) {
+                Ok(value) => Value::from(value),
+                Err(_) => Value::String(value.to_string())
+            }
+            TlbValue::Int(value) => match i64::try_from(*value) {
+                Ok(value) => Value::from(value),
+                Err(_) => Value::String(value.to_string())
+            }
+            TlbValue::Bits { data, len } => Value::String(bits_to_string(data, *len)),
+            TlbValue::Cell(cell) => Value::String(base64::encode(ton_types::serialize_toc(cell)?)),
+            TlbValue::Either { right, value } => {
+                let mut map = Map::new();
+                map.insert(if *right { "right" } else { "left" }.to_string(), value.to_json()?);
+                Value::Object(map)
+            }
+            TlbValue::List(values) => Value::Array(
+                values.iter().map(|value| value.to_json()).collect::<Result<Vec<_>>>()?
+            ),
+            TlbValue::Struct { constructor, fields, .. } => {
+                let mut map = Map::new();
+                map.insert("@type".to_string(), Value::String(constructor.clone()));
+                for (name, value) in fields {
+                    map.insert(name.clone(), value.to_json()?);
+                }
+                Value::Object(map)
+            }
+            TlbValue::Map { entries, extra } => {
+                let mut map = Map::new();
+                for entry in entries {
+                    let value = match &entry.extra {
+                        Some(extra) => {
+                            let mut leaf = Map::new();
+                            leaf.insert("value".to_string(), entry.value.to_json()?);
+                            leaf.insert("extra".to_string(), extra.to_json()?);
+                            Value::Object(leaf)
+                        }
+                        None => entry.value.to_json()?
+                    };
+                    map.insert(hex::encode(&entry.key), value);
+                }
+                match extra {
+                    Some(extra) => {
+                        let mut root = Map::new();
+                        root.insert("entries".to_string(), Value::Object(map));
+                        root.insert("extra".to_string(), extra.to_json()?);
+                        Value::Object(root)
+                    }
+                    None => Value::Object(map)
+                }
+            }
+        })
+    }
+}
+
+fn bits_to_string(data: &[u8], len: usize) -> String {
+    match len % 8 {
+        0 => hex::encode(data),
+        _ => format!("{}:{}", hex::encode(data), len)
+    }
+}
+
+fn bits_to_bytes(bits: &[bool]) -> Vec<u8> {
+    let mut data = vec![0; (bits.len() + 7) / 8];
+    for (i, bit) in bits.iter().enumerate() {
+        if *bit {
+            data[i / 8] |= 0x80 >> (i % 8);
+        }
+    }
+    data
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Schema text parser
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Token {
+    Ident(String),
+    Nat(u32),
+    Tag(char, String),
+    Sym(&'static str),
+}
+
+const SYMBOLS: [&str; 25] = [
+    "##", "#<=", "#<", "<=", ">=", "!=", "#", "(", ")", "[", "]", "{", "}",
+    "^", "=", ";", ":", "*", "+", "-", "?", "~", ".", "<", ">",
+];
+
+fn strip_comments(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while !rest.is_empty() {
+        if let Some(tail) = rest.strip_prefix("//") {
+            rest = tail.find('\n').map(|pos| &tail[pos..]).unwrap_or("");
+        } else if let Some(tail) = rest.strip_prefix("/*") {
+            rest = tail.find("*/").map(|pos| &tail[pos + 2..]).unwrap_or("");
+            result.push(' ');
+        } else {
+            let c = rest.chars().next().unwrap_or_default();
+            result.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    result
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '\''
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>> {
+    let text = strip_comments(text);
+    let mut tokens = Vec::new();
+    let mut rest = text.as_str();
+    'next: while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+            continue
+        }
+        if is_ident_char(c) {
+            let len = rest.find(|c| !is_ident_char(c)).unwrap_or(rest.len());
+            let word = &rest[..len];
+            rest = &rest[len..];
+            match word.parse::<u32>() {
+                Ok(nat) => tokens.push(Token::Nat(nat)),
+                Err(_) if word.starts_with(|c: char| c.is_ascii_digit()) => {
+                    fail!(BlockError::InvalidData(format!("wrong TL-B token `{}`", word)))
+                }
+                Err(_) => tokens.push(Token::Ident(word.to_string()))
+            }
+            // constructor tag follows the name without spaces
+            if let Some(kind) = rest.chars().next().filter(|c| *c == '#' || *c == '$') {
+                let tag = &rest[1..];
+                let len = tag.find(|c: char| !(c.is_ascii_hexdigit() || c == '_')).unwrap_or(tag.len());
+                tokens.push(Token::Tag(kind, tag[..len].to_string()));
+                rest = &tag[len..];
+            }
+            continue
+        }
+        for symbol in SYMBOLS {
+            if let Some(tail) = rest.strip_prefix(symbol) {
+                tokens.push(Token::Sym(symbol));
+                rest = tail;
+                continue 'next
+            }
+        }
+        fail!(BlockError::InvalidData(format!("unexpected character `{}` in TL-B schema", c)))
+    }
+    Ok(tokens)
+}
+
+fn parse_tag(kind: char, text: &str) -> Result<TlbTag> {
+    let mut bits = Vec::new();
+    let (digits, completion) = match text.strip_suffix('_') {
+        Some(digits) => (digits, true),
+        None => (text, false)
+    };
+    for c in digits.chars() {
+        match kind {
+            '$' => match c {
+                '0' | '1' => bits.push(c == '1'),
+                _ => fail!(BlockError::InvalidData(format!("wrong binary tag ${}", text)))
+            }
+            _ => {
+                let digit = c.to_digit(16).ok_or_else(|| error!(
+                    BlockError::InvalidData(format!("wrong hex tag #{}", text))
+                ))?;
+                (0..4).rev().for_each(|i| bits.push((digit >> i) & 1 != 0));
+            }
+        }
+    }
+    // hex tag with completion: trailing zeros and the last one bit are dropped
+    if completion && kind == '#' && !digits.is_empty() {
+        while bits.last() == Some(&false) {
+            bits.pop();
+        }
+        bits.pop();
+    }
+    if bits.len() > 64 {
+        fail!(BlockError::InvalidData(format!("too long constructor tag {}{}", kind, text)))
+    }
+    Ok(TlbTag {
+        bits: bits.iter().fold(0, |value, bit| (value << 1) | *bit as u64),
+        len: bits.len(),
+    })
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset)
+    }
+
+    fn next(&mut self) -> Result<Token> {
+        let token = self.tokens.get(self.pos).cloned().ok_or_else(|| error!(
+            BlockError::InvalidData("unexpected end of TL-B schema".to_string())
+        ))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn is_sym(&self, symbol: &str) -> bool {
+        matches!(self.peek(), Some(Token::Sym(s)) if *s == symbol)
+    }
+
+    fn expect_sym(&mut self, symbol: &str) -> Result<()> {
+        match self.next()? {
+            Token::Sym(s) if s == symbol => Ok(()),
+            token => fail!(BlockError::InvalidData(format!("expected `{}` but found {:?}", symbol, token)))
+        }
+    }
+
+    fn ident(&mut self) -> Result<String> {
+        match self.next()? {
+            Token::Ident(name) => Ok(name),
+            token => fail!(BlockError::InvalidData(format!("expected identifier but found {:?}", token)))
+        }
+    }
+
+    fn is_term_start(&self) -> bool {
+        match self.peek() {
+            Some(Token::Ident(_)) | Some(Token::Nat(_)) => true,
+            Some(Token::Sym(s)) => matches!(*s, "(" | "^" | "[" | "~" | "#" | "##" | "#<=" | "#<"),
+            _ => false
+        }
+    }
+
+    fn constructor(&mut self) -> Result<TlbConstructor> {
+        let name = self.ident()?;
+        let tag = match self.peek() {
+            Some(Token::Tag(kind, text)) => {
+                let tag = parse_tag(*kind, text)?;
+                self.pos += 1;
+                tag
+            }
+            _ => TlbTag::default()
+        };
+        let mut params = Vec::new();
+        let mut fields = Vec::new();
+        while !self.is_sym("=") {
+            if self.is_sym("{") {
+                if let Some(param) = self.braces()? {
+                    params.push(param);
+                }
+            } else {
+                fields.push(self.field()?);
+            }
+        }
+        self.expect_sym("=")?;
+        let type_name = self.ident()?;
+        let mut type_args = Vec::new();
+        while !self.is_sym(";") {
+            type_args.push(self.term()?);
+        }
+        self.expect_sym(";")?;
+        Ok(TlbConstructor { name, tag, params, fields, type_name, type_args })
+    }
+
+    // implicit parameter `{n:#}` is returned, constraints are skipped
+    fn braces(&mut self) -> Result<Option<(String, String)>> {
+        self.expect_sym("{")?;
+        let param = match (self.peek(), self.peek_at(1), self.peek_at(2)) {
+            (Some(Token::Ident(name)), Some(Token::Sym(":")), Some(kind)) => {
+                let kind = match kind {
+                    Token::Ident(kind) => kind.clone(),
+                    Token::Sym(kind) => kind.to_string(),
+                    _ => String::new()
+                };
+                Some((name.clone(), kind))
+            }
+            _ => None
+        };
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next()? {
+                Token::Sym("{") => depth += 1,
+                Token::Sym("}") => depth -= 1,
+                _ => ()
+            }
+        }
+        Ok(param)
+    }
+
+    fn field(&mut self) -> Result<TlbField> {
+        if let (Some(Token::Ident(name)), Some(Token::Sym(":"))) = (self.peek(), self.peek_at(1)) {
+            let name = match name.as_str() {
+                "_" => None,
+                name => Some(name.to_string())
+            };
+            self.pos += 2;
+            return Ok(TlbField { name, expr: self.term()? })
+        }
+        Ok(TlbField { name: None, expr: self.term()? })
+    }
+
+    fn term(&mut self) -> Result<TlbExpr> {
+        match self.next()? {
+            Token::Sym("^") => Ok(TlbExpr::Ref(Box::new(self.term()?))),
+            Token::Sym("~") => self.term(),
+            Token::Sym("#") => Ok(TlbExpr::Apply("#".to_string(), Vec::new())),
+            Token::Sym("##") => Ok(TlbExpr::NatBits(Box::new(self.term()?))),
+            Token::Sym("#<=") => Ok(TlbExpr::NatLeq(Box::new(self.term()?))),
+            Token::Sym("#<") => Ok(TlbExpr::NatLess(Box::new(self.term()?))),
+            Token::Sym("(") => {
+                let expr = self.expr()?;
+                self.expect_sym(")")?;
+                Ok(expr)
+            }
+            Token::Sym("[") => {
+                let mut fields = Vec::new();
+                while !self.is_sym("]") {
+                    if self.is_sym("{") {
+                        self.braces()?;
+                    } else {
+                        fields.push(self.field()?);
+                    }
+                }
+                self.expect_sym("]")?;
+                Ok(TlbExpr::Anon(fields))
+            }
+            Token::Nat(nat) => Ok(TlbExpr::Nat(nat)),
+            Token::Ident(name) => {
+                if self.is_sym("?") {
+                    self.pos += 1;
+                    return Ok(TlbExpr::Cond(name, None, Box::new(self.term()?)))
+                }
+                if let (Some(Token::Sym(".")), Some(Token::Nat(bit)), Some(Token::Sym("?"))) =
+                    (self.peek(), self.peek_at(1), self.peek_at(2))
+                {
+                    let bit = *bit;
+                    self.pos += 3;
+                    return Ok(TlbExpr::Cond(name, Some(bit), Box::new(self.term()?)))
+                }
+                Ok(TlbExpr::Apply(name, Vec::new()))
+            }
+            token => fail!(BlockError::InvalidData(format!("unexpected TL-B token {:?}", token)))
+        }
+    }
+
+    fn expr(&mut self) -> Result<TlbExpr> {
+        let mut expr = self.product()?;
+        while self.is_sym("+") {
+            self.pos += 1;
+            expr = TlbExpr::Add(Box::new(expr), Box::new(self.product()?));
+        }
+        Ok(expr)
+    }
+
+    fn product(&mut self) -> Result<TlbExpr> {
+        let mut expr = self.application()?;
+        while self.is_sym("*") {
+            self.pos += 1;
+            expr = TlbExpr::Mul(Box::new(expr), Box::new(self.application()?));
+        }
+        Ok(expr)
+    }
+
+    fn application(&mut self) -> Result<TlbExpr> {
+        match self.term()? {
+            TlbExpr::Apply(name, mut args) => {
+                while self.is_term_start() {
+                    args.push(self.term()?);
+                }
+                Ok(TlbExpr::Apply(name, args))
+            }
+            expr => Ok(expr)
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Schema reflection output
+
+impl fmt::Display for TlbTag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.len {
+            0 => write!(f, "$_"),
+            len if len % 4 == 0 => write!(f, "#{:0width$x}", self.bits, width = len / 4),
+            len => write!(f, "${:0width$b}", self.bits, width = len)
+        }
+    }
+}
+
+impl fmt::Display for TlbExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TlbExpr::Apply(name, args) if args.is_empty() => write!(f, "{}", name),
+            TlbExpr::Apply(name, args) => {
+                write!(f, "({}", name)?;
+                for arg in args {
+                    write!(f, " {}", arg)?;
+                }
+                write!(f, ")")
+            }
+            TlbExpr::Nat(nat) => write!(f, "{}", nat),
+            TlbExpr::Ref(expr) => write!(f, "^{}", expr),
+            TlbExpr::Anon(fields) => {
+                write!(f, "[")?;
+                for field in fields {
+                    write!(f, " {}", field)?;
+                }
+                write!(f, " ]")
+            }
+            TlbExpr::Mul(a, b) => write!(f, "({} * {})", a, b),
+            TlbExpr::Add(a, b) => write!(f, "({} + {})", a, b),
+            TlbExpr::NatLeq(expr) => write!(f, "(#<= {})", expr),
+            TlbExpr::NatLess(expr) => write!(f, "(#< {})", expr),
+            TlbExpr::NatBits(expr) => write!(f, "(## {})", expr),
+            TlbExpr::Cond(name, None, expr) => write!(f, "{}?{}", name, expr),
+            TlbExpr::Cond(name, Some(bit), expr) => write!(f, "{}.{}?{}", name, bit, expr),
+        }
+    }
+}
+
+impl fmt::Display for TlbField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.name.as_deref().unwrap_or("_"), self.expr)
+    }
+}
+
+impl fmt::Display for TlbConstructor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.name, self.tag)?;
+        for (name, kind) in &self.params {
+            write!(f, " {{{}:{}}}", name, kind)?;
+        }
+        for field in &self.fields {
+            write!(f, " {}", field)?;
+        }
+        write!(f, " = {}", self.type_name)?;
+        for arg in &self.type_args {
+            write!(f, " {}", arg)?;
+        }
+        write!(f, ";")
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Interpreter
+
+#[derive(Clone, Debug)]
+enum Binding {
+    Nat(u128),
+    Type(TlbExpr),
+}
+
+type Env = HashMap<String, Binding>;
+
+fn eval_nat(expr: &TlbExpr, env: &Env) -> Option<u128> {
+    match expr {
+        TlbExpr::Nat(nat) => Some(*nat as u128),
+        TlbExpr::Apply(name, args) if args.is_empty() => match env.get(name) {
+            Some(Binding::Nat(nat)) => Some(*nat),
+            _ => None
+        }
+        TlbExpr::Add(a, b) => eval_nat(a, env)?.checked_add(eval_nat(b, env)?),
+        TlbExpr::Mul(a, b) => eval_nat(a, env)?.checked_mul(eval_nat(b, env)?),
+        _ => None
+    }
+}
+
+// replace parameters of the caller by their values to pass type to other constructor
+fn substitute(expr: &TlbExpr, env: &Env) -> TlbExpr {
+    let boxed = |expr: &TlbExpr| Box::new(substitute(expr, env));
+    match expr {
+        TlbExpr::Apply(name, args) if args.is_empty() => match env.get(name) {
+            Some(Binding::Nat(nat)) => match u32::try_from(*nat) {
+                Ok(nat) => TlbExpr::Nat(nat),
+                Err(_) => expr.clone()
+            }
+            Some(Binding::Type(expr)) => expr.clone(),
+            None => expr.clone()
+        }
+        TlbExpr::Apply(name, args) => TlbExpr::Apply(
+            name.clone(),
+            args.iter().map(|arg| substitute(arg, env)).collect()
+        ),
+        TlbExpr::Nat(_) | TlbExpr::Anon(_) | TlbExpr::Cond(..) => expr.clone(),
+        TlbExpr::Ref(expr) => TlbExpr::Ref(boxed(expr.as_ref())),
+        TlbExpr::Mul(a, b) => TlbExpr::Mul(boxed(a.as_ref()), boxed(b.as_ref())),
+        TlbExpr::Add(a, b) => TlbExpr::Add(boxed(a.as_ref()), boxed(b.as_ref())),
+        TlbExpr::NatLeq(expr) => TlbExpr::NatLeq(boxed(expr.as_ref())),
+        TlbExpr::NatLess(expr) => TlbExpr::NatLess(boxed(expr.as_ref())),
+        TlbExpr::NatBits(expr) => TlbExpr::NatBits(boxed(expr.as_ref())),
+    }
+}
+
+fn bind(arg: &TlbExpr, env: &Env) -> Binding {
+    match eval_nat(arg, env) {
+        Some(nat) => Binding::Nat(nat),
+        None => Binding::Type(substitute(arg, env))
+    }
+}
+
+// bits needed to store numbers from 0 to n (#<= n)
+fn bits_for(n: u128) -> usize {
+    (128 - n.leading_zeros()) as usize
+}
+
+fn read_uint(slice: &mut SliceData, bits: usize) -> Result<u128> {
+    if bits > 128 {
+        fail!(BlockError::InvalidData(format!("number of {} bits is too long", bits)))
+    }
+    let mut value = 0u128;
+    let mut rest = bits;
+    while rest > 0 {
+        let chunk = std::cmp::min(rest, 64);
+        value = (value << chunk) | slice.get_next_int(chunk)? as u128;
+        rest -= chunk;
+    }
+    Ok(value)
+}
+
+fn read_bits(slice: &mut SliceData, len: usize) -> Result<TlbValue> {
+    Ok(TlbValue::Bits { data: slice.get_next_bits(len)?, len })
+}
+
+fn read_nat(slice: &mut SliceData, bits: usize) -> Result<TlbValue> {
+    match bits {
+        0..=128 => Ok(TlbValue::Nat(read_uint(slice, bits)?)),
+        _ => read_bits(slice, bits)
+    }
+}
+
+fn read_int(slice: &mut SliceData, bits: usize) -> Result<TlbValue> {
+    match bits {
+        0 => Ok(TlbValue::Int(0)),
+        1..=127 => {
+            let value = read_uint(slice, bits)? as i128;
+            match (value >> (bits - 1)) & 1 {
+                0 => Ok(TlbValue::Int(value)),
+                _ => Ok(TlbValue::Int(value - (1i128 << bits)))
+            }
+        }
+        128 => Ok(TlbValue::Int(read_uint(slice, bits)? as i128)),
+        _ => read_bits(slice, bits)
+    }
+}
+
+fn check_fully_parsed(slice: &SliceData, type_name: &dyn fmt::Display) -> Result<()> {
+    if slice.remaining_bits() != 0 || slice.remaining_references() != 0 {
+        fail!(BlockError::InvalidData(format!(
+            "{} bits and {} references are left after parsing {}",
+            slice.remaining_bits(), slice.remaining_references(), type_name
+        )))
+    }
+    Ok(())
+}
+
+// number with given suffix of the name, e.g. uint32
+fn name_suffix(name: &str, prefix: &str) -> Option<usize> {
+    name.strip_prefix(prefix)?.parse().ok()
+}
+
+///
+/// TL-B schema: set of constructors grouped by types.
+/// Supports explicit tags, implicit parameters, natural number arithmetic,
+/// conditional fields, anonymous constructors and builtin dictionary types.
+///
+#[derive(Clone, Debug, Default)]
+pub struct TlbSchema {
+    types: HashMap<String, Vec<TlbConstructor>>,
+    order: Vec<String>,
+}
+
+impl TlbSchema {
+    /// Parse schema text. Constructors without explicit tag have empty tag.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut parser = Parser { tokens: tokenize(text)?, pos: 0 };
+        let mut schema = Self::default();
+        while parser.peek().is_some() {
+            let constructor = parser.constructor()?;
+            if !schema.types.contains_key(&constructor.type_name) {
+                schema.order.push(constructor.type_name.clone());
+            }
+            schema.types.entry(constructor.type_name.clone()).or_default().push(constructor);
+        }
+        Ok(schema)
+    }
+
+    /// Schema of the types of this crate
+    pub fn builtin() -> &'static TlbSchema {
+        static SCHEMA: OnceLock<TlbSchema> = OnceLock::new();
+        SCHEMA.get_or_init(|| TlbSchema::parse(BLOCK_TLB).expect("embedded TL-B schema must be valid"))
+    }
+
+    /// Names of types in order of declaration
+    pub fn type_names(&self) -> &[String] {
+        &self.order
+    }
+
+    pub fn constructors(&self, type_name: &str) -> Option<&[TlbConstructor]> {
+        self.types.get(type_name).map(|constructors| constructors.as_slice())
+    }
+
+    /// Text of all constructors of the type
+    pub fn definition(&self, type_name: &str) -> Option<String> {
+        let constructors = self.constructors(type_name)?;
+        Some(constructors.iter().map(|c| c.to_string()).collect::<Vec<_>>().join("\n"))
+    }
+
+    /// Parse the cell as a value of type expression, e.g. `Message Any`
+    pub fn parse_cell(&self, cell: &Cell, type_expr: &str) -> Result<TlbValue> {
+        let mut slice = SliceData::load_cell(cell.clone())?;
+        let value = self.parse_slice(&mut slice, type_expr)?;
+        check_fully_parsed(&slice, &type_expr)?;
+        Ok(value)
+    }
+
+    /// Parse the beginning of the slice as a value of type expression
+    pub fn parse_slice(&self, slice: &mut SliceData, type_expr: &str) -> Result<TlbValue> {
+        let mut parser = Parser { tokens: tokenize(type_expr)?, pos: 0 };
+        let expr = parser.expr()?;
+        if let Some(token) = parser.peek() {
+            fail!(BlockError::InvalidArg(format!("unexpected {:?} in type `{}`", token, type_expr)))
+        }
+        self.read_expr(&expr, slice, &Env::new())
+    }
+
+    fn read_expr(&self, expr: &TlbExpr, slice: &mut SliceData, env: &Env) -> Result<TlbValue> {
+        match expr {
+            TlbExpr::Ref(inner) => {
+                let cell = slice.checked_drain_reference()?;
+                if matches!(inner.as_ref(), TlbExpr::Apply(name, args) if args.is_empty() && (name == "Cell" || name == "Any")) {
+                    return Ok(TlbValue::Cell(cell))
+                }
+                let mut child = SliceData::load_cell(cell)?;
+                let value = self.read_expr(inner, &mut child, env)?;
+                check_fully_parsed(&child, inner)?;
+                Ok(value)
+            }
+            TlbExpr::Anon(fields) => Ok(TlbValue::Struct {
+                type_name: String::new(),
+                constructor: "_".to_string(),
+                fields: self.read_fields(fields, slice, &mut env.clone())?,
+            }),
+            TlbExpr::Mul(count, item) => {
+                let count = self.nat(count, env)? as usize;
+                match item.as_ref() {
+                    TlbExpr::Apply(name, args) if name == "Bit" && args.is_empty() => read_bits(slice, count),
+                    item => {
+                        // count comes from data: item must consume something
+                        // or the loop can run for billions of iterations
+                        let mut list = Vec::new();
+                        for _ in 0..count {
+                            let remaining = (slice.remaining_bits(), slice.remaining_references());
+                            list.push(self.read_expr(item, slice, env)?);
+                            if remaining == (slice.remaining_bits(), slice.remaining_references()) {
+                                fail!(BlockError::InvalidData(
+                                    format!("repeated item `{}` consumes no data", item)
+                                ))
+                            }
+                        }
+                        Ok(TlbValue::List(list))
+                    }
+                }
+            }
+            TlbExpr::NatLeq(max) => read_nat(slice, bits_for(self.nat(max, env)?)),
+            TlbExpr::NatLess(max) => match self.nat(max, env)? {
+                0 => fail!(BlockError::InvalidData("type `#< 0` has no values".to_string())),
+                max => read_nat(slice, bits_for(max - 1))
+            }
+            TlbExpr::NatBits(bits) => read_nat(slice, self.nat(bits, env)? as usize),
+            TlbExpr::Cond(flag, bit, inner) => {
+                let flag = self.nat(&TlbExpr::Apply(flag.clone(), Vec::new()), env)?;
+                let set = match bit {
+                    Some(bit) => (flag >> bit) & 1 != 0,
+                    None => flag != 0
+                };
+                match set {
+                    true => self.read_expr(inner, slice, env),
+                    false => Ok(TlbValue::Null)
+                }
+            }
+            TlbExpr::Nat(_) | TlbExpr::Add(..) => {
+                fail!(BlockError::InvalidData(format!("`{}` is a number, not a type", expr)))
+            }
+            TlbExpr::Apply(name, args) => self.read_apply(name, args, slice, env),
+        }
+    }
+
+    fn nat(&self, expr: &TlbExpr, env: &Env) -> Result<u128> {
+        eval_nat(expr, env).ok_or_else(|| error!(
+            BlockError::InvalidData(format!("cannot evaluate `{}` as natural number", expr))
+        ))
+    }
+
+    fn read_apply(&self, name: &str, args: &[TlbExpr], slice: &mut SliceData, env: &Env) -> Result<TlbValue> {
+        if args.is_empty() {
+            if let Some(Binding::Type(expr)) = env.get(name) {
+                return self.read_expr(expr, slice, env)
+            }
+        }
+        let value = match (name, args) {
+            ("#", []) => read_nat(slice, 32)?,
+            ("Bit" | "Bool", []) => TlbValue::Bool(slice.get_next_bit()?),
+            ("Cell" | "Any", []) => {
+                let cell = slice.clone().into_cell();
+                *slice = SliceData::default();
+                TlbValue::Cell(cell)
+            }
+            ("Maybe", [inner]) => match slice.get_next_bit()? {
+                true => self.read_expr(inner, slice, env)?,
+                false => TlbValue::Null
+            }
+            ("Either", [left, right]) => {
+                let is_right = slice.get_next_bit()?;
+                let value = self.read_expr(if is_right { right } else { left }, slice, env)?;
+                TlbValue::Either { right: is_right, value: Box::new(value) }
+            }
+            ("Both", [first, second]) => TlbValue::List(vec![
+                self.read_expr(first, slice, env)?,
+                self.read_expr(second, slice, env)?,
+            ]),
+            ("Unary", []) => {
+                let mut value = 0;
+                while slice.get_next_bit()? {
+                    value += 1;
+                }
+                TlbValue::Nat(value)
+            }
+            ("Grams" | "Coins", []) => self.read_var_uint(16, false, slice)?,
+            ("VarUInteger", [n]) => self.read_var_uint(self.nat(n, env)?, false, slice)?,
+            ("VarInteger", [n]) => self.read_var_uint(self.nat(n, env)?, true, slice)?,
+            ("uint", [bits]) => read_nat(slice, self.nat(bits, env)? as usize)?,
+            ("int", [bits]) => read_int(slice, self.nat(bits, env)? as usize)?,
+            ("bits", [bits]) => read_bits(slice, self.nat(bits, env)? as usize)?,
+            ("Hashmap", [n, value]) => {
+                let mut entries = Vec::new();
+                self.read_hashmap(slice, self.nat(n, env)? as usize, value, None, env, &mut Vec::new(), &mut entries)?;
+                TlbValue::Map { entries, extra: None }
+            }
+            ("HashmapAug", [n, value, extra]) => {
+                let mut entries = Vec::new();
+                let extra = self.read_hashmap(slice, self.nat(n, env)? as usize, value, Some(extra), env, &mut Vec::new(), &mut entries)?;
+                TlbValue::Map { entries, extra: extra.map(Box::new) }
+            }
+            ("HashmapE", [n, value]) => {
+                let mut entries = Vec::new();
+                if slice.get_next_bit()? {
+                    let mut root = SliceData::load_cell(slice.checked_drain_reference()?)?;
+                    self.read_hashmap(&mut root, self.nat(n, env)? as usize, value, None, env, &mut Vec::new(), &mut entries)?;
+                    check_fully_parsed(&root, &name)?;
+                }
+                TlbValue::Map { entries, extra: None }
+            }
+            ("HashmapAugE", [n, value, extra]) => {
+                let mut entries = Vec::new();
+                if slice.get_next_bit()? {
+                    let mut root = SliceData::load_cell(slice.checked_drain_reference()?)?;
+                    self.read_hashmap(&mut root, self.nat(n, env)? as usize, value, Some(extra), env, &mut Vec::new(), &mut entries)?;
+                    check_fully_parsed(&root, &name)?;
+                }
+                let extra = self.read_expr(extra, slice, env)?;
+                TlbValue::Map { entries, extra: Some(Box::new(extra)) }
+            }
+            (name, []) if name_suffix(name, "uint").is_some() => read_nat(slice, name_suffix(name, "uint").unwrap_or_default())?,
+            (name, []) if name_suffix(name, "int").is_some() => read_int(slice, name_suffix(name, "int").unwrap_or_default())?,
+            (name, []) if name_suffix(name, "bits").is_some() => read_bits(slice, name_suffix(name, "bits").unwrap_or_default())?,
+            (name, args) => {
+                let args = args.iter().map(|arg| bind(arg, env)).collect::<Vec<_>>();
+                self.read_type(name, &args, slice)?
+            }
+        };
+        Ok(value)
+    }
+
+    fn read_var_uint(&self, n: u128, signed: bool, slice: &mut SliceData) -> Result<TlbValue> {
+        if n == 0 {
+            fail!(BlockError::InvalidData("VarUInteger 0 has no values".to_string()))
+        }
+        let len = read_uint(slice, bits_for(n - 1))? as usize;
+        match signed {
+            true => read_int(slice, len * 8),
+            false => read_nat(slice, len * 8)
+        }
+    }
+
+    // hm_edge: label is followed by leaf value or fork with two references,
+    // augmented dictionaries store extra after fork references and before leaf value
+    #[allow(clippy::too_many_arguments)]
+    fn read_hashmap(
+        &self,
+        slice: &mut SliceData,
+        bit_len: usize,
+        value: &TlbExpr,
+        extra: Option<&TlbExpr>,
+        env: &Env,
+        key: &mut Vec<bool>,
+        entries: &mut Vec<TlbMapEntry>,
+    ) -> Result<Option<TlbValue>> {
+        let prefix_len = key.len();
+        let label_bits = bits_for(bit_len as u128);
+        if !slice.get_next_bit()? {
+            // hml_short$0 len:(Unary ~n) s:(n * Bit)
+            let mut len = 0;
+            while slice.get_next_bit()? {
+                len += 1;
+            }
+            for _ in 0..len {
+                key.push(slice.get_next_bit()?);
+            }
+        } else if !slice.get_next_bit()? {
+            // hml_long$10 n:(#<= m) s:(n * Bit)
+            let len = read_uint(slice, label_bits)?;
+            for _ in 0..len {
+                key.push(slice.get_next_bit()?);
+            }
+        } else {
+            // hml_same$11 v:Bit n:(#<= m)
+            let bit = slice.get_next_bit()?;
+            let len = read_uint(slice, label_bits)? as usize;
+            key.extend(std::iter::repeat(bit).take(len));
+        }
+        let label_len = key.len() - prefix_len;
+        if label_len > bit_len {
+            fail!(BlockError::InvalidData(format!("hashmap label of {} bits is longer than {}", label_len, bit_len)))
+        }
+        let rest = bit_len - label_len;
+        let node_extra = if rest == 0 {
+            let leaf_extra = extra.map(|extra| self.read_expr(extra, slice, env)).transpose()?;
+            let value = self.read_expr(value, slice, env)?;
+            entries.push(TlbMapEntry {
+                key: bits_to_bytes(key),
+                value,
+                extra: leaf_extra.clone(),
+            });
+            leaf_extra
+        } else {
+            for bit in [false, true] {
+                let mut child = SliceData::load_cell(slice.checked_drain_reference()?)?;
+                key.push(bit);
+                self.read_hashmap(&mut child, rest - 1, value, extra, env, key, entries)?;
+                key.pop();
+                check_fully_parsed(&child, &"hashmap node")?;
+            }
+            extra.map(|extra| self.read_expr(extra, slice, env)).transpose()?
+        };
+        key.truncate(prefix_len);
+        Ok(node_extra)
+    }
+
+    fn read_type(&self, type_name: &str, args: &[Binding], slice: &mut SliceData) -> Result<TlbValue> {
+        let constructors = self.constructors(type_name).ok_or_else(|| error!(
+            BlockError::InvalidArg(format!("unknown TL-B type `{}`", type_name))
+        ))?;
+        // longer tags are checked first: constructors with common prefix differ by tail
+        let mut candidates = constructors.iter().collect::<Vec<_>>();
+        candidates.sort_by(|a, b| b.tag.len.cmp(&a.tag.len));
+        let mut last_error = None;
+        for constructor in candidates {
+            let tag = constructor.tag;
+            if slice.remaining_bits() < tag.len || (tag.len > 0 && slice.clone().get_next_int(tag.len)? != tag.bits) {
+                continue
+            }
+            let mut env = match Self::bind_type_args(constructor, args) {
+                Some(env) => env,
+                None => continue
+            };
+            let mut cursor = slice.clone();
+            if tag.len > 0 {
+                cursor.get_next_int(tag.len)?;
+            }
+            match self.read_fields(&constructor.fields, &mut cursor, &mut env) {
+                Ok(fields) => {
+                    *slice = cursor;
+                    return Ok(TlbValue::Struct {
+                        type_name: type_name.to_string(),
+                        constructor: constructor.name.clone(),
+                        fields,
+                    })
+                }
+                Err(err) => last_error = Some(err)
+            }
+        }
+        match last_error {
+            Some(err) => Err(err),
+            None => fail!(BlockError::InvalidData(format!("no constructor of `{}` matches data", type_name)))
+        }
+    }
+
+    // match arguments of the type with result of constructor, e.g. `= BlkPrevInfo 0` or `= Message X`
+    fn bind_type_args(constructor: &TlbConstructor, args: &[Binding]) -> Option<Env> {
+        let mut env = Env::new();
+        for (expected, arg) in constructor.type_args.iter().zip(args) {
+            match (expected, arg) {
+                (TlbExpr::Nat(expected), Binding::Nat(arg)) => if *expected as u128 != *arg {
+                    return None
+                }
+                (TlbExpr::Nat(_), Binding::Type(_)) => return None,
+                (TlbExpr::Apply(name, params), arg) if params.is_empty() => {
+                    env.insert(name.clone(), arg.clone());
+                }
+                (TlbExpr::Add(param, value), Binding::Nat(arg)) => {
+                    if let (TlbExpr::Apply(name, _), TlbExpr::Nat(value)) = (param.as_ref(), value.as_ref()) {
+                        let param = arg.checked_sub(*value as u128)?;
+                        env.insert(name.clone(), Binding::Nat(param));
+                    }
+                }
+                _ => ()
+            }
+        }
+        Some(env)
+    }
+
+    fn read_fields(&self, fields: &[TlbField], slice: &mut SliceData, env: &mut Env) -> Result<Vec<(String, TlbValue)>> {
+        let mut result = Vec::with_capacity(fields.len());
+        for field in fields {
+            let value = self.read_expr(&field.expr, slice, env)?;
+            if let (Some(name), Some(nat)) = (&field.name, value.as_nat()) {
+                env.insert(name.clone(), Binding::Nat(nat));
+            }
+            result.push((field.name.clone().unwrap_or_else(|| "_".to_string()), value));
+        }
+        Ok(result)
+    }
+}
+
+///
+/// Type with TL-B definition in the builtin schema
+///
+pub trait TlbType: Serializable {
+    /// type expression in the schema, e.g. `Message Any`
+    const TLB_TYPE: &'static str;
+
+    fn tlb_type_name() -> &'static str {
+        Self::TLB_TYPE.split_whitespace().next().unwrap_or_default()
+    }
+
+    fn tlb_constructors() -> &'static [TlbConstructor] {
+        TlbSchema::builtin().constructors(Self::tlb_type_name()).unwrap_or_default()
+    }
+
+    fn tlb_definition() -> String {
+        TlbSchema::builtin().definition(Self::tlb_type_name()).unwrap_or_default()
+    }
+
+    /// Serialize and parse the value by the schema
+    fn tlb_tree(&self) -> Result<TlbValue> {
+        TlbSchema::builtin().parse_cell(&self.serialize()?, Self::TLB_TYPE)
+    }
+}
+
+macro_rules! impl_tlb_type {
+    ($($type:ty => $tlb:expr),* $(,)?) => {
+        $(
+            impl TlbType for $type {
+                const TLB_TYPE: &'static str = $tlb;
+            }
+        )*
+    };
+}
+
+impl_tlb_type! {
+    MsgAddressExt => "MsgAddressExt",
+    MsgAddressInt => "MsgAddressInt",
+    MsgAddress => "MsgAddress",
+    ExtraCurrencyCollection => "ExtraCurrencyCollection",
+    CurrencyCollection => "CurrencyCollection",
+    CommonMsgInfo => "CommonMsgInfo",
+    TickTock => "TickTock",
+    SimpleLib => "SimpleLib",
+    StateInit => "StateInit",
+    Message => "Message Any",
+    StorageUsed => "StorageUsed",
+    StorageInfo => "StorageInfo",
+    AccountState => "AccountState",
+    AccountStatus => "AccountStatus",
+    Account => "Account",
+    ShardAccount => "ShardAccount",
+    DepthBalanceInfo => "DepthBalanceInfo",
+    ShardAccounts => "ShardAccounts",
+    ShardIdent => "ShardIdent",
+    BlockIdExt => "BlockIdExt",
+    ExtBlkRef => "ExtBlkRef",
+    SigPubKey => "SigPubKey",
+    ValidatorDescr => "ValidatorDescr",
+    ValidatorSet => "ValidatorSet",
+    ConfigParams => "ConfigParams",
+    GlobalVersion => "GlobalVersion",
+    ConfigProposal => "ConfigProposal",
+    CryptoSignature => "CryptoSignature",
+    CryptoSignaturePair => "CryptoSignaturePair",
+    ValidatorBaseInfo => "ValidatorBaseInfo",
+    ValidatorInfo => "ValidatorInfo",
+    BlockSignaturesPure => "BlockSignaturesPure",
+    BlockSignatures => "BlockSignatures",
+    BlockProof => "BlockProof",
+    AccStatusChange => "AccStatusChange",
+    ComputeSkipReason => "ComputeSkipReason",
+    StorageUsedShort => "StorageUsedShort",
+    TrStoragePhase => "TrStoragePhase",
+    TrCreditPhase => "TrCreditPhase",
+    TrComputePhase => "TrComputePhase",
+    TrActionPhase => "TrActionPhase",
+    TrBouncePhase => "TrBouncePhase",
+    SplitMergeInfo => "SplitMergeInfo",
+    TransactionDescr => "TransactionDescr",
+    HashUpdate => "HASH_UPDATE Any",
+    Transaction => "Transaction",
+    AccountBlock => "AccountBlock",
+    ShardAccountBlocks => "ShardAccountBlocks",
+    IntermediateAddress => "IntermediateAddress",
+    MsgEnvelope => "MsgEnvelope",
+    ImportFees => "ImportFees",
+    InMsg => "InMsg",
+    InMsgDescr => "InMsgDescr",
+    OutMsg => "OutMsg",
+    OutMsgDescr => "OutMsgDescr",
+    EnqueuedMsg => "EnqueuedMsg",
+    OutMsgQueue => "OutMsgQueue",
+    ProcessedUpto => "ProcessedUpto",
+    ProcessedInfo => "ProcessedInfo",
+    IhrPendingSince => "IhrPendingSince",
+    IhrPendingInfo => "IhrPendingInfo",
+    OutMsgQueueInfo => "OutMsgQueueInfo",
+    BlkMasterInfo => "BlkMasterInfo",
+    BlockInfo => "BlockInfo",
+    ValueFlow => "ValueFlow",
+    TopBlockDescr => "TopBlockDescr",
+    TopBlockDescrSet => "TopBlockDescrSet",
+    FutureSplitMerge => "FutureSplitMerge",
+    ShardDescr => "ShardDescr",
+    ShardHashes => "ShardHashes",
+    ShardFeeCreated => "ShardFeeCreated",
+    ShardFees => "ShardFees",
+    McBlockExtra => "McBlockExtra",
+    KeyMaxLt => "KeyMaxLt",
+    KeyExtBlkRef => "KeyExtBlkRef",
+    OldMcBlocksInfo => "OldMcBlocksInfo",
+    Counters => "Counters",
+    CreatorStats => "CreatorStats",
+    BlockCreateStats => "BlockCreateStats",
+    McStateExtra => "McStateExtra",
+    BlockExtra => "BlockExtra",
+    OutQueueUpdate => "OutQueueUpdate",
+    Block => "Block",
+    LibDescr => "LibDescr",
+    ShardStateUnsplit => "ShardStateUnsplit",
+    ShardState => "ShardState",
+    ConfigParam0 => "ConfigParam 0",
+    ConfigParam1 => "ConfigParam 1",
+    ConfigParam2 => "ConfigParam 2",
+    ConfigParam3 => "ConfigParam 3",
+    ConfigParam4 => "ConfigParam 4",
+    ConfigParam5 => "ConfigParam 5",
+    ConfigParam6 => "ConfigParam 6",
+    ConfigParam7 => "ConfigParam 7",
+    ConfigParam8 => "ConfigParam 8",
+    ConfigParam9 => "ConfigParam 9",
+    ConfigParam10 => "ConfigParam 10",
+    ConfigProposalSetup => "ConfigProposalSetup",
+    ConfigVotingSetup => "ConfigVotingSetup",
+    WorkchainDescr => "WorkchainDescr",
+    ConfigParam12 => "ConfigParam 12",
+    ConfigParam13 => "ConfigParam 13",
+    BlockCreateFees => "BlockCreateFees",
+    ConfigParam14 => "ConfigParam 14",
+    ConfigParam15 => "ConfigParam 15",
+    ConfigParam16 => "ConfigParam 16",
+    ConfigParam17 => "ConfigParam 17",
+    StoragePrices => "StoragePrices",
+    ConfigParam18 => "ConfigParam 18",
+    ConfigParam19 => "ConfigParam 19",
+    GasLimitsPrices => "GasLimitsPrices",
+    ParamLimits => "ParamLimits",
+    BlockLimits => "BlockLimits",
+    MsgForwardPrices => "MsgForwardPrices",
+    CatchainConfig => "CatchainConfig",
+    ConsensusConfig => "ConsensusConfig",
+    ConfigParam29 => "ConfigParam 29",
+    DelectorParams => "DelectorParams",
+    ConfigParam31 => "ConfigParam 31",
+    ConfigParam32 => "ConfigParam 32",
+    ConfigParam33 => "ConfigParam 33",
+    ConfigParam34 => "ConfigParam 34",
+    ConfigParam35 => "ConfigParam 35",
+    ConfigParam36 => "ConfigParam 36",
+    ConfigParam37 => "ConfigParam 37",
+    ValidatorTempKey => "ValidatorTempKey",
+    ValidatorSignedTempKey => "ValidatorSignedTempKey",
+    ConfigParam39 => "ConfigParam 39",
+    SlashingConfig => "SlashingConfig",
+    ConfigParam40 => "ConfigParam 40",
+    ConfigCopyleft => "ConfigCopyleft",
+    SizeLimitsConfig => "SizeLimitsConfig",
+    PrecompiledSmc => "PrecompiledSmc",
+    PrecompiledContractsConfig => "PrecompiledContractsConfig",
+    OracleBridgeParams => "OracleBridgeParams",
+    JettonBridgePrices => "JettonBridgePrices",
+    JettonBridgeParams => "JettonBridgeParams",
+}
+
+#[cfg(test)]
+#[path = "tests/test_tlb.rs"]
+mod tests;
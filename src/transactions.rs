@@ -1439,6 +1439,14 @@ impl Transaction {
         self.in_msg.as_ref().map(|c| c.cell())
     }
 
+    /// Normalized hash of inbound external message of the transaction
+    pub fn in_msg_normalized_hash(&self) -> Result<Option<UInt256>> {
+        match self.read_in_msg()? {
+            Some(msg) if msg.is_inbound_external() => Ok(Some(msg.normalized_hash()?)),
+            _ => Ok(None)
+        }
+    }
+
     /// get output message by index
     pub fn get_out_msg(&self, index: i16) -> Result<Option<Message>> {
         Ok(self.out_msgs.get(&U15(index))?.map(|msg| msg.0))